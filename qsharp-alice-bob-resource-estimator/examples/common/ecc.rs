@@ -0,0 +1,19 @@
+// Copyright (c) Alice & Bob
+// Licensed under the MIT License.
+
+//! Elliptic curve cryptography workload shared by the examples.
+
+use qsharp_alice_bob_resource_estimator::LogicalCounts;
+
+/// Compute logical qubits number and logical gates counts for elliptic curve
+/// discrete logarithm computation, based on <https://arxiv.org/abs/2302.06639>.
+#[allow(clippy::similar_names)]
+pub fn elliptic_curve_crypto_count(bit_size: u64, window_size: u64) -> LogicalCounts {
+    // Number of qubits for discrete log computation, arXiv:2302.06639 (p. 22, app C.11)
+    let qubit_count = 9 * bit_size + window_size + 4;
+    // Asymptotic gate counts, arXiv:2302.06639 (p. 21, app C.10)
+    let cx_count = (448 * bit_size.pow(3)).div_ceil(window_size);
+    let ccx_count = (348 * bit_size.pow(3)).div_ceil(window_size);
+
+    LogicalCounts::new(qubit_count, cx_count, ccx_count)
+}
@@ -9,26 +9,17 @@
 //! Based on É. Gouzien et al.'s article (<https://arxiv.org/abs/2302.06639>)
 //! and code (<https://github.com/ElieGouzien/elliptic_log_cat/tree/master>).
 
+#[path = "common/ecc.rs"]
+mod ecc;
+
 use std::rc::Rc;
 
+use ecc::elliptic_curve_crypto_count;
 use qsharp_alice_bob_resource_estimator::{
-    AliceAndBobEstimates, CatQubit, LogicalCounts, RepetitionCode, ToffoliBuilder,
+    AliceAndBobEstimates, CatQubit, RepetitionCode, ToffoliBuilder,
 };
 use resource_estimator::estimates::{ErrorBudget, PhysicalResourceEstimation};
 
-/// Compute logical qubits number and logical gates counts for elliptic curve
-/// discrete logarithm computation, based on <https://arxiv.org/abs/2302.06639>.
-#[allow(clippy::similar_names)]
-fn elliptic_curve_crypto_count(bit_size: u64, window_size: u64) -> LogicalCounts {
-    // Number of qubits for discrete log computation, arXiv:2302.06639 (p. 22, app C.11)
-    let qubit_count = 9 * bit_size + window_size + 4;
-    // Asymptotic gate counts, arXiv:2302.06639 (p. 21, app C.10)
-    let cx_count = (448 * bit_size.pow(3)).div_ceil(window_size);
-    let ccx_count = (348 * bit_size.pow(3)).div_ceil(window_size);
-
-    LogicalCounts::new(qubit_count, cx_count, ccx_count)
-}
-
 /// Estimate resources for EC Shor algorithm from pre-computed counts.
 fn main() -> Result<(), anyhow::Error> {
     // This value can be changed to investigate other key sizes, e.g., those in
@@ -29,5 +29,20 @@ fn main() -> Result<(), anyhow::Error> {
     println!("Resource estimate from Q# code (ripple-carry adder):");
     println!("{result}");
 
+    // The adder is only simulated once, thanks to `BeginEstimateCaching`.
+    let filename = format!("{}/qsharp/RepeatedAdder.qs", env!("CARGO_MANIFEST_DIR"));
+
+    let qubit = CatQubit::new();
+    let qec = RepetitionCode::new();
+    let builder = ToffoliBuilder::default();
+    let count = LogicalCounts::from_qsharp(filename).map_err(anyhow::Error::msg)?;
+    let budget = ErrorBudget::new(0.001 * 0.5, 0.001 * 0.5, 0.0);
+
+    let estimation =
+        PhysicalResourceEstimation::new(qec, Rc::new(qubit), builder, Rc::new(count), budget);
+    let result: AliceAndBobEstimates = estimation.estimate()?.into();
+    println!("Resource estimate from Q# code (10000 cached ripple-carry adders):");
+    println!("{result}");
+
     Ok(())
 }
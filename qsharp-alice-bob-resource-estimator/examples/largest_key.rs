@@ -0,0 +1,57 @@
+// Copyright (c) Alice & Bob
+// Licensed under the MIT License.
+
+#![warn(missing_docs)]
+//! Find the largest Elliptic Curve Cryptography (ECC) key size whose discrete
+//! logarithm can be computed on a cat-based quantum processor with a given
+//! number of physical qubits and within a given runtime.
+//!
+//! Based on É. Gouzien et al.'s article (<https://arxiv.org/abs/2302.06639>).
+
+#[path = "common/ecc.rs"]
+mod ecc;
+
+use ecc::elliptic_curve_crypto_count;
+use qsharp_alice_bob_resource_estimator::{
+    analysis::{BudgetSplit, Constraints},
+    estimates::units::{Count, Runtime},
+    ModelParameters,
+};
+
+/// Largest key size for several numbers of physical qubits, within a day.
+fn main() -> Result<(), anyhow::Error> {
+    // Window size w_e = 18 as reported for 256 bits in arXiv:2302.06639
+    // (Table IV, p. 37), not optimized for the other key sizes
+    let window_size = 18;
+    let budget = BudgetSplit {
+        logical: 0.333 * 0.5,
+        magic_states: 0.333 * 0.5,
+        rotations: 0.0,
+    };
+    let workload = |bit_size| Ok(elliptic_curve_crypto_count(bit_size, window_size));
+
+    println!("{:>8} {:>9} {:>11}", "qubits", "key size", "runtime");
+    for max_physical_qubits in [50_000, 100_000, 200_000, 500_000] {
+        let constraints = Constraints {
+            max_physical_qubits: Some(max_physical_qubits),
+            max_runtime: Some(86_400_000_000_000),
+        };
+        let largest = ModelParameters::default()
+            .largest_instance(&workload, 8..=4096, &budget, &constraints)
+            .map_err(anyhow::Error::msg)?;
+        let fastest = largest
+            .estimates
+            .iter()
+            .map(|estimates| estimates.runtime())
+            .min()
+            .unwrap_or_default();
+        println!(
+            "{:>8} {:>9} {:>11}",
+            Count(max_physical_qubits),
+            largest.size,
+            Runtime(fastest)
+        );
+    }
+
+    Ok(())
+}
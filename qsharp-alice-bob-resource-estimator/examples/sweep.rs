@@ -0,0 +1,74 @@
+// Copyright (c) Alice & Bob
+// Licensed under the MIT License.
+
+#![warn(missing_docs)]
+//! Estimate the resources required for Elliptic Curve Cryptography (ECC) on a
+//! cat-based quantum processor, for several key sizes, physical error rates
+//! and error budgets, in parallel.
+//!
+//! Based on É. Gouzien et al.'s article (<https://arxiv.org/abs/2302.06639>).
+
+#[path = "common/ecc.rs"]
+mod ecc;
+
+use ecc::elliptic_curve_crypto_count;
+use qsharp_alice_bob_resource_estimator::{
+    analysis::{BudgetSplit, Sweep},
+    estimates::units::{Count, Runtime},
+    ModelParameters,
+};
+
+/// Sweep key sizes, κ₁/κ₂ and error budgets.
+fn main() {
+    // Window size w_e = 18 as reported for 256 bits in arXiv:2302.06639
+    // (Table IV, p. 37), not optimized for the other key sizes
+    let window_size = 18;
+    let sweep = [128, 192, 256, 384, 521]
+        .into_iter()
+        .fold(Sweep::new(), |sweep, bit_size| {
+            sweep.with_workload(
+                format!("ECC-{bit_size}"),
+                elliptic_curve_crypto_count(bit_size, window_size),
+            )
+        })
+        .with_parameters(
+            [1e-6, 1e-5, 1e-4]
+                .into_iter()
+                .map(|k1_k2| ModelParameters {
+                    k1_k2,
+                    ..ModelParameters::default()
+                })
+                .collect(),
+        )
+        .with_budgets(
+            [0.01, 0.1, 0.333]
+                .into_iter()
+                .map(|total| BudgetSplit {
+                    logical: total * 0.5,
+                    magic_states: total * 0.5,
+                    rotations: 0.0,
+                })
+                .collect(),
+        );
+
+    println!(
+        "{:>8} {:>7} {:>6} {:>10} {:>11}",
+        "workload", "κ₁/κ₂", "budget", "qubits", "runtime"
+    );
+    for result in sweep.run(None) {
+        let prefix = format!(
+            "{:>8} {:>7.0e} {:>6}",
+            result.workload,
+            result.parameters.k1_k2,
+            result.budget.total()
+        );
+        match result.estimate {
+            Some(report) => println!(
+                "{prefix} {:>10} {:>11}",
+                Count(report.physical_qubits.total),
+                Runtime(report.runtime_ns)
+            ),
+            None => println!("{prefix} {}", result.error.unwrap_or_default()),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Analyses of the estimates, built on repeated estimations of a workload
+//! with different model parameters.
+//!
+//! [`ModelParameters`] gathers the assumptions of the hardware and code models
+//! that are not measured precisely: κ₁/κ₂ and κ₂ of the cat qubits (see
+//! [`crate::qubit`]), the threshold and prefactor of the repetition code (see
+//! [`crate::code`]), the duration of the logical operations (see
+//! [`crate::counter::cycles`]) and the error probabilities of the magic state
+//! factories (see [`crate::factories`]).
+//!
+//! Available analyses:
+//! - [`budget`]: split of the error budget minimizing qubits or runtime.
+//! - [`constraints`]: best estimate within a limit on qubits or runtime.
+//! - [`inverse`]: largest instance of a workload, or hardware requirement,
+//!   within such limits.
+//! - [`pareto`]: frontier over the qubits, the runtime and the total error.
+//! - [`sensitivity`]: which assumption drives the result.
+//! - [`uncertainty`]: spread of the results when the parameters are uncertain.
+//!
+//! Many estimations can also be run in parallel with a [`Sweep`] (see
+//! [`sweep`]).
+
+use std::rc::Rc;
+
+use resource_estimator::estimates::{ErrorBudget, PhysicalResourceEstimation};
+use serde::{Deserialize, Serialize};
+
+use crate::{AliceAndBobEstimates, CatQubit, LogicalCounts, RepetitionCode, ToffoliBuilder};
+
+pub use budget::{BudgetSplit, Objective};
+pub use constraints::Constraints;
+pub use inverse::{CostModelWorkload, LargestInstance, QubitRequirement, Workload};
+pub use pareto::BudgetSweep;
+pub use sensitivity::{Parameter, Sensitivity, SensitivityReport};
+pub use sweep::{Sweep, SweepResult};
+pub use uncertainty::{Distribution, Interval, UncertainParameters, UncertaintyReport};
+
+pub mod budget;
+pub mod constraints;
+pub mod inverse;
+pub mod pareto;
+pub mod sensitivity;
+pub mod sweep;
+pub mod uncertainty;
+
+/// Assumptions of the hardware and code models.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelParameters {
+    /// Ratio κ₁/κ₂ between the one and two photons loss rates.
+    pub k1_k2: f64,
+    /// Two photons loss rate κ₂, in s⁻¹.
+    pub k2: f64,
+    /// Threshold (κ₁/κ₂)_th of the repetition code.
+    pub threshold: f64,
+    /// Prefactor of the logical phase-flip probability.
+    pub prefactor: f64,
+    /// Factor applied to the duration of all the logical operations.
+    pub cycle_scale: f64,
+    /// Factor applied to the error probabilities of the factories, on top of
+    /// their scaling with κ₁/κ₂ (see [`ModelParameters::factories`]).
+    pub factory_error_scale: f64,
+}
+
+impl Default for ModelParameters {
+    fn default() -> Self {
+        let qubit = CatQubit::default();
+        let code = RepetitionCode::default();
+        Self {
+            k1_k2: qubit.k1_k2(),
+            k2: qubit.k2(),
+            threshold: code.threshold(),
+            prefactor: code.prefactor(),
+            cycle_scale: 1.0,
+            factory_error_scale: 1.0,
+        }
+    }
+}
+
+impl ModelParameters {
+    /// Cat qubits with these parameters.
+    pub fn qubit(&self) -> CatQubit {
+        CatQubit::new().with_k1_k2(self.k1_k2).with_k2(self.k2)
+    }
+
+    /// Repetition code with these parameters.
+    #[must_use]
+    pub fn code(&self) -> RepetitionCode {
+        RepetitionCode::new()
+            .with_threshold(self.threshold)
+            .with_prefactor(self.prefactor)
+    }
+
+    /// Magic state factories with these parameters.
+    ///
+    /// The error probabilities of the factories are precomputed for the
+    /// default κ₁/κ₂ of [`CatQubit`] (see [`crate::factories`]): they are
+    /// scaled linearly with κ₁/κ₂, to first order in the physical error rate,
+    /// and then by `factory_error_scale`.
+    #[must_use]
+    pub fn factories(&self) -> ToffoliBuilder {
+        let k1_k2_scale = self.k1_k2 / CatQubit::default().k1_k2();
+        ToffoliBuilder::default().with_error_scale(k1_k2_scale * self.factory_error_scale)
+    }
+
+    /// `counts` with the durations of the logical operations of these
+    /// parameters.
+    pub fn counts(&self, counts: &LogicalCounts) -> LogicalCounts {
+        counts
+            .clone()
+            .with_cycle_costs(counts.cycle_costs().scaled(self.cycle_scale))
+    }
+
+    /// Estimate the physical resources of `counts` with these parameters.
+    ///
+    /// # Errors
+    /// If no code parameter or factory reaches the error `budget`.
+    pub fn estimate(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+    ) -> Result<AliceAndBobEstimates, String> {
+        self.estimation(counts, budget)
+            .estimate()
+            .map(|result| AliceAndBobEstimates::with_budget(result, budget))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Estimation of `counts` with these parameters, for the error `budget`
+    /// adjusted to the idle error model of `counts`.
+    fn estimation(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+    ) -> PhysicalResourceEstimation<RepetitionCode, ToffoliBuilder, LogicalCounts> {
+        let counts = self.counts(counts);
+        let budget = counts.adjusted_error_budget(budget);
+        PhysicalResourceEstimation::new(
+            self.code(),
+            Rc::new(self.qubit()),
+            self.factories(),
+            Rc::new(counts),
+            budget,
+        )
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Split of the error budget between logical errors and magic states.
+//!
+//! A larger logical share lowers the code distance (fewer qubits, shorter
+//! cycles), a larger magic state share allows smaller factories: the best
+//! split depends on the workload. The total error is split on a regular grid
+//! of logical shares, the workload is estimated for each split, and the one
+//! minimizing the [`Objective`] is kept.
+//!
+//! Rotations are not used by this architecture (see
+//! [`crate::estimates::errors`]), hence their share is always zero. A workload
+//! without magic states gives the whole budget to logical errors.
+
+use std::fmt::Display;
+
+use num_traits::ToPrimitive;
+use resource_estimator::estimates::{ErrorBudget, Overhead};
+use serde::Serialize;
+
+use super::ModelParameters;
+use crate::{AliceAndBobEstimates, LogicalCounts};
+
+/// Quantity to minimize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    /// Number of physical qubits.
+    PhysicalQubits,
+    /// Runtime.
+    Runtime,
+    /// Product of the number of physical qubits and the runtime.
+    Product,
+}
+
+impl Objective {
+    /// Value of the objective for `estimates`.
+    #[must_use]
+    pub fn value(self, estimates: &AliceAndBobEstimates) -> f64 {
+        let qubits = estimates.physical_qubits().to_f64().expect("can't convert");
+        let runtime = estimates.runtime().to_f64().expect("can't convert");
+        match self {
+            Self::PhysicalQubits => qubits,
+            Self::Runtime => runtime,
+            Self::Product => qubits * runtime,
+        }
+    }
+}
+
+/// Split of a total error budget.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct BudgetSplit {
+    /// Budget for the logical errors.
+    pub logical: f64,
+    /// Budget for the magic states errors.
+    pub magic_states: f64,
+    /// Budget for the rotations errors.
+    pub rotations: f64,
+}
+
+impl BudgetSplit {
+    /// Total error budget.
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.logical + self.magic_states + self.rotations
+    }
+
+    /// Error budget of the split.
+    #[must_use]
+    pub fn budget(&self) -> ErrorBudget {
+        ErrorBudget::new(self.logical, self.magic_states, self.rotations)
+    }
+}
+
+impl Display for BudgetSplit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = self.total();
+        write!(
+            f,
+            "logical {:.3e} ({:.0}%), magic states {:.3e} ({:.0}%), rotations {:.3e}",
+            self.logical,
+            self.logical / total * 100.0,
+            self.magic_states,
+            self.magic_states / total * 100.0,
+            self.rotations
+        )
+    }
+}
+
+impl ModelParameters {
+    /// Estimate `counts` with the split of `total_error` minimizing
+    /// `objective`, among `steps` logical shares evenly spaced in (0, 1).
+    ///
+    /// # Errors
+    /// If `total_error` is not positive, or if no split can be estimated.
+    pub fn optimize_budget(
+        &self,
+        counts: &LogicalCounts,
+        total_error: f64,
+        objective: Objective,
+        steps: usize,
+    ) -> Result<(BudgetSplit, AliceAndBobEstimates), String> {
+        if total_error <= 0.0 {
+            return Err(format!(
+                "total error budget must be positive, got {total_error}"
+            ));
+        }
+
+        let magic_states = counts.num_magic_states(&ErrorBudget::new(total_error, 0.0, 0.0), 0);
+        let splits: Vec<BudgetSplit> = if magic_states == 0 {
+            vec![BudgetSplit {
+                logical: total_error,
+                magic_states: 0.0,
+                rotations: 0.0,
+            }]
+        } else {
+            let steps = steps.max(1);
+            let denominator = (steps + 1).to_f64().expect("can't convert");
+            (1..=steps)
+                .map(|i| {
+                    let share = i.to_f64().expect("can't convert") / denominator;
+                    BudgetSplit {
+                        logical: total_error * share,
+                        magic_states: total_error * (1.0 - share),
+                        rotations: 0.0,
+                    }
+                })
+                .collect()
+        };
+
+        let mut best: Option<(f64, BudgetSplit, AliceAndBobEstimates)> = None;
+        let mut last_failure = None;
+        for split in splits {
+            match self.estimate(counts, &split.budget()) {
+                Ok(estimates) => {
+                    let value = objective.value(&estimates);
+                    match &best {
+                        Some((best, _, _)) if *best <= value => {}
+                        _ => best = Some((value, split, estimates)),
+                    }
+                }
+                Err(e) => last_failure = Some(e),
+            }
+        }
+
+        best.map(|(_, split, estimates)| (split, estimates))
+            .ok_or_else(|| {
+                format!(
+                    "no split of the error budget {total_error} can be estimated (last error: {})",
+                    last_failure.unwrap_or_default()
+                )
+            })
+    }
+}
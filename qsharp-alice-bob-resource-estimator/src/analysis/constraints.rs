@@ -0,0 +1,145 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Best estimate within a limit on the physical qubits or on the runtime.
+//!
+//! The frontier of the estimation (see
+//! [`PhysicalResourceEstimation::build_frontier`]) trades qubits for time: it
+//! goes from few factory copies and a long runtime to many copies and a short
+//! runtime, with the smallest code distance for each. The estimates of the
+//! frontier are checked against the [`Constraints`], with the number of
+//! physical qubits of [`AliceAndBobEstimates::physical_qubits`], routing
+//! qubits included, and the one minimizing the [`Objective`] is kept.
+//!
+//! When no estimate of the frontier satisfies the constraints, the error tells
+//! the smallest and fastest estimates, to show how far the limits are.
+//!
+//! [`PhysicalResourceEstimation::build_frontier`]:
+//! resource_estimator::estimates::PhysicalResourceEstimation::build_frontier
+
+use std::fmt::Display;
+
+use resource_estimator::estimates::ErrorBudget;
+use serde::{Deserialize, Serialize};
+
+use super::{ModelParameters, Objective};
+use crate::{
+    estimates::units::{Count, Runtime},
+    AliceAndBobEstimates, LogicalCounts,
+};
+
+/// Hard limits on the resources.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Constraints {
+    /// Maximum number of physical qubits, routing qubits included.
+    pub max_physical_qubits: Option<u64>,
+    /// Maximum runtime, in nanoseconds.
+    pub max_runtime: Option<u64>,
+}
+
+impl Constraints {
+    /// Whether `estimates` is within the limits.
+    #[must_use]
+    pub fn satisfied_by(&self, estimates: &AliceAndBobEstimates) -> bool {
+        let within =
+            |limit: Option<u64>, value: u64| !matches!(limit, Some(limit) if value > limit);
+        within(self.max_physical_qubits, estimates.physical_qubits())
+            && within(self.max_runtime, estimates.runtime())
+    }
+
+    /// Estimates of `frontier` within the limits.
+    ///
+    /// # Errors
+    /// If no estimate is within the limits, with the smallest and fastest
+    /// estimates of `frontier`.
+    pub fn filter(
+        &self,
+        frontier: Vec<AliceAndBobEstimates>,
+    ) -> Result<Vec<AliceAndBobEstimates>, String> {
+        let summary = self.unsatisfiable(&frontier);
+        let satisfied: Vec<_> = frontier
+            .into_iter()
+            .filter(|estimates| self.satisfied_by(estimates))
+            .collect();
+        if satisfied.is_empty() {
+            Err(summary)
+        } else {
+            Ok(satisfied)
+        }
+    }
+
+    /// Error message when no estimate of `frontier` is within the limits.
+    fn unsatisfiable(&self, frontier: &[AliceAndBobEstimates]) -> String {
+        let smallest = frontier.iter().min_by_key(|e| e.physical_qubits());
+        let fastest = frontier.iter().min_by_key(|e| e.runtime());
+        match (smallest, fastest) {
+            (Some(smallest), Some(fastest)) => format!(
+                "no configuration satisfies the constraints ({self}): the smallest one needs {} \
+                 physical qubits ({} runtime), the fastest one {} runtime ({} physical qubits)",
+                Count(smallest.physical_qubits()),
+                Runtime(smallest.runtime()),
+                Runtime(fastest.runtime()),
+                Count(fastest.physical_qubits()),
+            ),
+            _ => format!("no configuration satisfies the constraints ({self}): empty frontier"),
+        }
+    }
+}
+
+impl Display for Constraints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.max_physical_qubits, self.max_runtime) {
+            (None, None) => write!(f, "no constraint"),
+            (Some(qubits), None) => write!(f, "at most {} physical qubits", Count(qubits)),
+            (None, Some(runtime)) => write!(f, "runtime of at most {}", Runtime(runtime)),
+            (Some(qubits), Some(runtime)) => write!(
+                f,
+                "at most {} physical qubits and runtime of at most {}",
+                Count(qubits),
+                Runtime(runtime)
+            ),
+        }
+    }
+}
+
+impl ModelParameters {
+    /// Frontier of the estimates of `counts`, trading qubits for runtime.
+    ///
+    /// # Errors
+    /// If no code parameter or factory reaches the error `budget`.
+    pub fn frontier(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+    ) -> Result<Vec<AliceAndBobEstimates>, String> {
+        self.estimation(counts, budget)
+            .build_frontier()
+            .map(|frontier| {
+                frontier
+                    .into_iter()
+                    .map(|result| AliceAndBobEstimates::with_budget(result, budget))
+                    .collect()
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Estimate of the frontier of `counts` within `constraints` minimizing
+    /// `objective`.
+    ///
+    /// # Errors
+    /// If the workload cannot be estimated, or if no estimate satisfies the
+    /// constraints.
+    pub fn estimate_with_constraints(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+        constraints: &Constraints,
+        objective: Objective,
+    ) -> Result<AliceAndBobEstimates, String> {
+        let frontier = constraints.filter(self.frontier(counts, budget)?)?;
+        Ok(frontier
+            .into_iter()
+            .min_by(|a, b| objective.value(a).total_cmp(&objective.value(b)))
+            .expect("filtered frontier is not empty"))
+    }
+}
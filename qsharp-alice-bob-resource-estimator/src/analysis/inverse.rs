@@ -0,0 +1,304 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Inverse problems: what fits within given resources.
+//!
+//! Instead of estimating the resources of a workload, the resources are fixed
+//! by [`Constraints`] (a number of physical qubits, a maximum runtime, or
+//! both), and the largest instance of a parameterized [`Workload`] that fits
+//! is searched. An instance fits if an estimate of its frontier (see
+//! [`ModelParameters::frontier`]) satisfies the constraints.
+//!
+//! The search is over the integer size of the instance (e.g. the key size of
+//! ECC, or a parameter of a [`CostModel`], see [`CostModelWorkload`]), and
+//! assumes that the resources grow with the size: the size is doubled from
+//! the smallest one until an instance does not fit, then the last interval is
+//! bisected. Sizes much larger than the answer, whose counts may overflow the
+//! estimation, are thus never estimated.
+//!
+//! Likewise, for a fixed workload, the hardware requirement to meet the
+//! constraints is searched: the largest κ₁/κ₂ (see
+//! [`ModelParameters::max_k1_k2`]) or the smallest κ₂ (see
+//! [`ModelParameters::min_k2`]) of the cat qubits, the other parameters being
+//! unchanged. The search is a bisection of the logarithm of the parameter,
+//! down to a relative precision of 0.1%, which assumes that the resources
+//! grow with κ₁/κ₂ and decrease with κ₂. The error probabilities of the magic
+//! state factories follow κ₁/κ₂ (see [`ModelParameters::factories`]). If the
+//! constraints are met over the whole search range, the parameter is not
+//! limiting, and no value is reported.
+
+use std::{collections::HashMap, fmt::Display, ops::RangeInclusive};
+
+use num_traits::ToPrimitive;
+use serde::Serialize;
+
+use resource_estimator::estimates::ErrorBudget;
+
+use super::{BudgetSplit, Constraints, ModelParameters, Parameter};
+use crate::{AliceAndBobEstimates, CostModel, LogicalCounts};
+
+/// Workload parameterized by an integer size.
+pub trait Workload {
+    /// Logical counts of the instance of size `size`.
+    ///
+    /// # Errors
+    /// If the instance cannot be counted.
+    fn counts(&self, size: u64) -> Result<LogicalCounts, String>;
+}
+
+impl<F: Fn(u64) -> Result<LogicalCounts, String>> Workload for F {
+    fn counts(&self, size: u64) -> Result<LogicalCounts, String> {
+        self(size)
+    }
+}
+
+/// Cost model whose size is one of its parameters, the other ones being
+/// fixed.
+pub struct CostModelWorkload<'a> {
+    model: &'a CostModel,
+    parameter: String,
+    values: HashMap<String, f64>,
+}
+
+impl<'a> CostModelWorkload<'a> {
+    /// Workload of `model` with size `parameter`, and `values` for the other
+    /// parameters.
+    ///
+    /// # Errors
+    /// If `parameter` is not a parameter of `model`.
+    pub fn new(
+        model: &'a CostModel,
+        parameter: impl Into<String>,
+        values: HashMap<String, f64>,
+    ) -> Result<Self, String> {
+        let parameter = parameter.into();
+        if !model.parameters().contains(&parameter.as_str()) {
+            return Err(format!(
+                "'{parameter}' is not a parameter of the model (parameters: {})",
+                model.parameters().join(", ")
+            ));
+        }
+        Ok(Self {
+            model,
+            parameter,
+            values,
+        })
+    }
+}
+
+impl Workload for CostModelWorkload<'_> {
+    fn counts(&self, size: u64) -> Result<LogicalCounts, String> {
+        let mut values = self.values.clone();
+        values.insert(
+            self.parameter.clone(),
+            size.to_f64().expect("can't convert"),
+        );
+        self.model.counts(&values)
+    }
+}
+
+/// Largest instance of a workload within constraints.
+pub struct LargestInstance {
+    /// Size of the instance.
+    pub size: u64,
+    /// Estimates of the frontier of the instance within the constraints.
+    pub estimates: Vec<AliceAndBobEstimates>,
+}
+
+impl ModelParameters {
+    /// Largest size of `workload` in `sizes` that has an estimate within
+    /// `constraints`, for the error `budget`.
+    ///
+    /// # Errors
+    /// If there is no constraint, if `sizes` is empty, if the smallest size
+    /// does not fit, or if an instance cannot be counted.
+    pub fn largest_instance(
+        &self,
+        workload: &impl Workload,
+        sizes: RangeInclusive<u64>,
+        budget: &BudgetSplit,
+        constraints: &Constraints,
+    ) -> Result<LargestInstance, String> {
+        if *constraints == Constraints::default() {
+            return Err("no constraint on the physical qubits or the runtime".to_string());
+        }
+        let (mut low, max) = sizes.into_inner();
+        if low > max {
+            return Err(format!("empty range of sizes [{low}, {max}]"));
+        }
+
+        // estimates within the constraints, or the reason why there is none
+        let fits = |size: u64| -> Result<Result<Vec<AliceAndBobEstimates>, String>, String> {
+            let counts = workload.counts(size)?;
+            Ok(self
+                .frontier(&counts, &budget.budget())
+                .and_then(|frontier| constraints.filter(frontier)))
+        };
+
+        let mut estimates = fits(low)?
+            .map_err(|e| format!("the smallest instance (size {low}) does not fit: {e}"))?;
+
+        // double the step until an instance does not fit, so that sizes much
+        // larger than the answer are never estimated
+        let mut step = 1;
+        let mut high = None;
+        while high.is_none() && low < max {
+            let next = low.saturating_add(step).min(max);
+            match fits(next)? {
+                Ok(found) => {
+                    low = next;
+                    estimates = found;
+                    step = step.saturating_mul(2);
+                }
+                Err(_) => high = Some(next),
+            }
+        }
+
+        // `low` fits, `high` does not
+        if let Some(mut high) = high {
+            while high - low > 1 {
+                let middle = low + (high - low) / 2;
+                match fits(middle)? {
+                    Ok(found) => {
+                        low = middle;
+                        estimates = found;
+                    }
+                    Err(_) => high = middle,
+                }
+            }
+        }
+
+        Ok(LargestInstance {
+            size: low,
+            estimates,
+        })
+    }
+}
+
+/// Hardware requirement on a parameter of the cat qubits.
+#[derive(Serialize)]
+pub struct QubitRequirement {
+    /// κ₁/κ₂ or κ₂.
+    pub parameter: Parameter,
+    /// Largest κ₁/κ₂ or smallest κ₂ meeting the constraints, or `None` if
+    /// the parameter is not limiting within the search range.
+    pub value: Option<f64>,
+    /// Estimates of the frontier for this value (or for the bound of the
+    /// search range if the parameter is not limiting) within the constraints.
+    #[serde(skip)]
+    pub estimates: Vec<AliceAndBobEstimates>,
+}
+
+impl Display for QubitRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(value) = self.value else {
+            return write!(f, "{}: not limiting", self.parameter.name());
+        };
+        let bound = if self.parameter == Parameter::K1K2 {
+            "max"
+        } else {
+            "min"
+        };
+        write!(f, "{bound} {}: {value:.3e}", self.parameter.name())
+    }
+}
+
+/// Relative precision of the bisection of the hardware requirements.
+const PRECISION: f64 = 1e-3;
+
+impl ModelParameters {
+    /// Largest κ₁/κ₂, between 1e-9 and the threshold of the code, for which
+    /// `counts` has an estimate within `constraints`, or `None` if the
+    /// constraints are met up to the threshold.
+    ///
+    /// # Errors
+    /// If there is no constraint, or if the constraints are not met with
+    /// κ₁/κ₂ = 1e-9.
+    pub fn max_k1_k2(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+        constraints: &Constraints,
+    ) -> Result<QubitRequirement, String> {
+        self.requirement(
+            Parameter::K1K2,
+            counts,
+            budget,
+            constraints,
+            1e-9,
+            self.threshold,
+        )
+    }
+
+    /// Smallest κ₂, between 1e3 s⁻¹ and 1e10 s⁻¹, for which `counts` has an
+    /// estimate within `constraints`, or `None` if the constraints are met
+    /// down to 1e3 s⁻¹.
+    ///
+    /// # Errors
+    /// If there is no constraint, or if the constraints are not met with
+    /// κ₂ = 1e10 s⁻¹.
+    pub fn min_k2(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+        constraints: &Constraints,
+    ) -> Result<QubitRequirement, String> {
+        self.requirement(Parameter::K2, counts, budget, constraints, 1e10, 1e3)
+    }
+
+    /// Bisection of `parameter` between `good`, which must meet the
+    /// constraints, and `bad`; the parameter is not limiting if `bad` also
+    /// meets them.
+    fn requirement(
+        &self,
+        parameter: Parameter,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+        constraints: &Constraints,
+        mut good: f64,
+        mut bad: f64,
+    ) -> Result<QubitRequirement, String> {
+        if *constraints == Constraints::default() {
+            return Err("no constraint on the physical qubits or the runtime".to_string());
+        }
+
+        // estimates within the constraints, or the reason why there is none
+        let fits = |value: f64| {
+            parameter
+                .scaled(self, value / parameter.value(self))
+                .frontier(counts, budget)
+                .and_then(|frontier| constraints.filter(frontier))
+        };
+
+        let mut estimates = fits(good).map_err(|e| {
+            format!(
+                "the constraints are not met even with {} = {good:e}: {e}",
+                parameter.name()
+            )
+        })?;
+        if let Ok(estimates) = fits(bad) {
+            return Ok(QubitRequirement {
+                parameter,
+                value: None,
+                estimates,
+            });
+        }
+
+        while (bad / good).ln().abs() > PRECISION {
+            let middle = (good * bad).sqrt();
+            match fits(middle) {
+                Ok(found) => {
+                    good = middle;
+                    estimates = found;
+                }
+                Err(_) => bad = middle,
+            }
+        }
+
+        Ok(QubitRequirement {
+            parameter,
+            value: Some(good),
+            estimates,
+        })
+    }
+}
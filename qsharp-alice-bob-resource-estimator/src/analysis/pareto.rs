@@ -0,0 +1,143 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Frontier over the physical qubits, the runtime and the total error.
+//!
+//! The frontier of the estimation (see [`ModelParameters::frontier`]) trades
+//! qubits for runtime at a fixed error budget. To choose an operating point,
+//! the total error budget is swept over a [`BudgetSweep`] (log-spaced values),
+//! the frontier is built for each budget, and the estimates dominated by
+//! another one (not worse on any of the three objectives, and better on at
+//! least one) are removed. The total error of an estimate is the one actually
+//! reached, below its budget.
+//!
+//! Each estimate keeps its budget and code and factory parameters, which are
+//! exported with its report (see [`crate::estimates::report`]).
+
+use num_traits::ToPrimitive;
+use resource_estimator::estimates::ErrorBudget;
+use serde::{Deserialize, Serialize};
+
+use super::ModelParameters;
+use crate::{AliceAndBobEstimates, LogicalCounts};
+
+/// Total error budgets of the sweep.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BudgetSweep {
+    /// Smallest total error budget.
+    pub min_total: f64,
+    /// Largest total error budget.
+    pub max_total: f64,
+    /// Number of budgets, log-spaced between the smallest and the largest.
+    pub steps: usize,
+    /// Share of the budget given to logical errors, the rest going to the
+    /// magic states.
+    pub logical_share: f64,
+}
+
+impl Default for BudgetSweep {
+    fn default() -> Self {
+        Self {
+            min_total: 1e-4,
+            max_total: 0.5,
+            steps: 20,
+            logical_share: 0.5,
+        }
+    }
+}
+
+impl BudgetSweep {
+    /// Error budgets of the sweep.
+    #[must_use]
+    pub fn budgets(&self) -> Vec<ErrorBudget> {
+        let totals: Vec<f64> = if self.steps <= 1 {
+            vec![self.max_total]
+        } else {
+            let last = (self.steps - 1).to_f64().expect("can't convert");
+            let ratio = (self.max_total / self.min_total).ln();
+            (0..self.steps)
+                .map(|i| {
+                    let i = i.to_f64().expect("can't convert");
+                    self.min_total * (ratio * i / last).exp()
+                })
+                .collect()
+        };
+        totals
+            .into_iter()
+            .map(|total| {
+                ErrorBudget::new(
+                    total * self.logical_share,
+                    total * (1.0 - self.logical_share),
+                    0.0,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Objectives of an estimate, all to be minimized.
+fn objectives(estimates: &AliceAndBobEstimates) -> [f64; 3] {
+    [
+        estimates.physical_qubits().to_f64().expect("can't convert"),
+        estimates.runtime().to_f64().expect("can't convert"),
+        estimates.total_error(),
+    ]
+}
+
+/// Whether objectives `a` dominate objectives `b`.
+fn dominates(a: &[f64; 3], b: &[f64; 3]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a <= b) && a.iter().zip(b).any(|(a, b)| a < b)
+}
+
+impl ModelParameters {
+    /// Estimates of `counts` not dominated on the physical qubits, the runtime
+    /// and the total error, over the budgets of `sweep`, sorted by number of
+    /// physical qubits, then runtime.
+    ///
+    /// # Errors
+    /// If `counts` cannot be estimated for any budget of the sweep.
+    pub fn error_frontier(
+        &self,
+        counts: &LogicalCounts,
+        sweep: &BudgetSweep,
+    ) -> Result<Vec<AliceAndBobEstimates>, String> {
+        if !(sweep.min_total > 0.0 && sweep.min_total <= sweep.max_total) {
+            return Err(format!(
+                "invalid range of error budgets [{}, {}]",
+                sweep.min_total, sweep.max_total
+            ));
+        }
+
+        let mut candidates = vec![];
+        let mut last_failure = None;
+        for budget in sweep.budgets() {
+            match self.frontier(counts, &budget) {
+                Ok(frontier) => candidates.extend(frontier),
+                Err(e) => last_failure = Some(e),
+            }
+        }
+        if candidates.is_empty() {
+            return Err(format!(
+                "no error budget of the sweep can be estimated (last error: {})",
+                last_failure.unwrap_or_default()
+            ));
+        }
+
+        let points: Vec<[f64; 3]> = candidates.iter().map(objectives).collect();
+        let mut frontier: Vec<(AliceAndBobEstimates, [f64; 3])> = vec![];
+        for (index, (estimates, point)) in candidates.into_iter().zip(&points).enumerate() {
+            let dominated = points.iter().any(|other| dominates(other, point));
+            // keep a single estimate among equal ones
+            let duplicate = points[..index].contains(point);
+            if !dominated && !duplicate {
+                frontier.push((estimates, *point));
+            }
+        }
+
+        frontier.sort_by(|(_, a), (_, b)| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+        Ok(frontier
+            .into_iter()
+            .map(|(estimates, _)| estimates)
+            .collect())
+    }
+}
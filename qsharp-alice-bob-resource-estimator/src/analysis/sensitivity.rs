@@ -0,0 +1,221 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Sensitivity of the estimates to the model parameters.
+//!
+//! Each parameter of [`ModelParameters`] is perturbed in turn by a relative
+//! step h, all the other ones being unchanged, and the workload is estimated
+//! again. The elasticity of a result y (physical qubits or runtime) to a
+//! parameter x is given by central differences:
+//!
+//! e = (y(x·(1 + h)) − y(x·(1 − h))) / (2h · y(x)),
+//!
+//! i.e. the relative change of y for a 1% change of x is about e%. The code
+//! distance and number of factories are integers, so the elasticities are
+//! zero when the perturbation does not change them, and large when it does:
+//! they show which assumptions the result depends on, not a smooth derivative.
+
+use std::fmt::Display;
+
+use num_traits::ToPrimitive;
+use resource_estimator::estimates::ErrorBudget;
+use serde::Serialize;
+
+use super::ModelParameters;
+use crate::{estimates::units::Count, estimates::units::Runtime, LogicalCounts};
+
+/// Model parameter perturbed by the sensitivity analysis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Parameter {
+    /// κ₁/κ₂ of the cat qubits, which also scales the factory errors.
+    K1K2,
+    /// κ₂ of the cat qubits.
+    K2,
+    /// Threshold of the repetition code.
+    Threshold,
+    /// Prefactor of the logical phase-flip probability.
+    Prefactor,
+    /// Duration of the logical operations.
+    CycleCosts,
+    /// Error probabilities of the factories.
+    FactoryErrors,
+}
+
+impl Parameter {
+    /// All the parameters, in the order of the analysis.
+    pub const ALL: [Self; 6] = [
+        Self::K1K2,
+        Self::K2,
+        Self::Threshold,
+        Self::Prefactor,
+        Self::CycleCosts,
+        Self::FactoryErrors,
+    ];
+
+    /// Name of the parameter.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::K1K2 => "κ₁/κ₂",
+            Self::K2 => "κ₂",
+            Self::Threshold => "threshold",
+            Self::Prefactor => "prefactor",
+            Self::CycleCosts => "cycle costs",
+            Self::FactoryErrors => "factory errors",
+        }
+    }
+
+    /// Value of the parameter in `parameters`.
+    #[must_use]
+    pub fn value(self, parameters: &ModelParameters) -> f64 {
+        match self {
+            Self::K1K2 => parameters.k1_k2,
+            Self::K2 => parameters.k2,
+            Self::Threshold => parameters.threshold,
+            Self::Prefactor => parameters.prefactor,
+            Self::CycleCosts => parameters.cycle_scale,
+            Self::FactoryErrors => parameters.factory_error_scale,
+        }
+    }
+
+    /// `parameters` with the value of the parameter multiplied by `factor`.
+    #[must_use]
+    pub fn scaled(self, parameters: &ModelParameters, factor: f64) -> ModelParameters {
+        let mut parameters = *parameters;
+        let value = match self {
+            Self::K1K2 => &mut parameters.k1_k2,
+            Self::K2 => &mut parameters.k2,
+            Self::Threshold => &mut parameters.threshold,
+            Self::Prefactor => &mut parameters.prefactor,
+            Self::CycleCosts => &mut parameters.cycle_scale,
+            Self::FactoryErrors => &mut parameters.factory_error_scale,
+        };
+        *value *= factor;
+        parameters
+    }
+}
+
+/// Elasticities of the results to one parameter.
+#[derive(Clone, Debug, Serialize)]
+pub struct Sensitivity {
+    /// Perturbed parameter.
+    pub parameter: Parameter,
+    /// Value of the parameter.
+    pub value: f64,
+    /// Elasticity of the number of physical qubits.
+    pub physical_qubits: f64,
+    /// Elasticity of the runtime.
+    pub runtime: f64,
+}
+
+/// Results of a sensitivity analysis.
+#[derive(Clone, Debug, Serialize)]
+pub struct SensitivityReport {
+    /// Relative step of the perturbations.
+    pub relative_step: f64,
+    /// Number of physical qubits with the unperturbed parameters.
+    pub physical_qubits: u64,
+    /// Runtime in nanoseconds with the unperturbed parameters.
+    pub runtime_ns: u64,
+    /// Elasticities, in the order of [`Parameter::ALL`].
+    pub sensitivities: Vec<Sensitivity>,
+}
+
+impl SensitivityReport {
+    /// Parameter with the largest elasticity, in absolute value, of the
+    /// number of physical qubits or of the runtime.
+    #[must_use]
+    pub fn dominant(&self) -> Option<&Sensitivity> {
+        self.sensitivities.iter().max_by(|a, b| {
+            let magnitude = |s: &Sensitivity| s.physical_qubits.abs().max(s.runtime.abs());
+            magnitude(a).total_cmp(&magnitude(b))
+        })
+    }
+}
+
+impl ModelParameters {
+    /// Elasticities of the physical qubits and runtime of `counts` to each
+    /// parameter, for perturbations of `relative_step` (e.g. 0.1 for ±10%).
+    ///
+    /// # Errors
+    /// If the workload cannot be estimated with one of the perturbed
+    /// parameters.
+    pub fn sensitivity(
+        &self,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+        relative_step: f64,
+    ) -> Result<SensitivityReport, String> {
+        if !(relative_step > 0.0 && relative_step < 1.0) {
+            return Err(format!(
+                "relative step must be between 0 and 1, got {relative_step}"
+            ));
+        }
+
+        let to_f64 = |n: u64| n.to_f64().expect("can't convert");
+        let base = self.estimate(counts, budget)?;
+        let (qubits, runtime) = (base.physical_qubits(), base.runtime());
+
+        let sensitivities = Parameter::ALL
+            .into_iter()
+            .map(|parameter| {
+                let estimate = |factor: f64| {
+                    parameter
+                        .scaled(self, factor)
+                        .estimate(counts, budget)
+                        .map_err(|e| format!("{} × {factor}: {e}", parameter.name()))
+                };
+                let up = estimate(1.0 + relative_step)?;
+                let down = estimate(1.0 - relative_step)?;
+                let elasticity = |up: u64, down: u64, base: u64| {
+                    (to_f64(up) - to_f64(down)) / (2.0 * relative_step * to_f64(base))
+                };
+
+                Ok(Sensitivity {
+                    parameter,
+                    value: parameter.value(self),
+                    physical_qubits: elasticity(
+                        up.physical_qubits(),
+                        down.physical_qubits(),
+                        qubits,
+                    ),
+                    runtime: elasticity(up.runtime(), down.runtime(), runtime),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(SensitivityReport {
+            relative_step,
+            physical_qubits: qubits,
+            runtime_ns: runtime,
+            sensitivities,
+        })
+    }
+}
+
+impl Display for SensitivityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "─────────────────────────────")?;
+        writeln!(f, "# qubits:            {}", Count(self.physical_qubits))?;
+        writeln!(f, "runtime:             {}", Runtime(self.runtime_ns))?;
+        writeln!(
+            f,
+            "elasticities (±{}%): qubits   runtime",
+            self.relative_step * 100.0
+        )?;
+        for s in &self.sensitivities {
+            writeln!(
+                f,
+                "  {:<18}{:>8.3} {:>9.3}",
+                s.parameter.name(),
+                s.physical_qubits,
+                s.runtime
+            )?;
+        }
+        if let Some(dominant) = self.dominant() {
+            writeln!(f, "dominant parameter:  {}", dominant.parameter.name())?;
+        }
+        writeln!(f, "─────────────────────────────")
+    }
+}
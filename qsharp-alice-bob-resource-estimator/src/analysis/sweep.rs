@@ -0,0 +1,166 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Parallel estimation over a grid of workloads, model parameters and error
+//! budgets.
+//!
+//! The estimation API shares the qubit and the counts through `Rc`, hence an
+//! estimation cannot be sent to another thread. A [`Sweep`] sends the inputs
+//! instead ([`LogicalCounts`], [`ModelParameters`] and [`BudgetSplit`] are
+//! thread-safe): each worker thread builds its own estimations, and returns
+//! plain [`EstimateReport`]s. The points of the grid are handed out to the
+//! workers one at a time, and the results are returned in the order of the
+//! grid (workloads, then parameters, then budgets), whatever the number of
+//! threads.
+
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use serde::Serialize;
+
+use super::{BudgetSplit, ModelParameters};
+use crate::{EstimateReport, LogicalCounts};
+
+/// Grid of estimations.
+#[derive(Clone)]
+#[must_use]
+pub struct Sweep {
+    workloads: Vec<(String, LogicalCounts)>,
+    parameters: Vec<ModelParameters>,
+    budgets: Vec<BudgetSplit>,
+}
+
+impl Default for Sweep {
+    /// No workload, the default model parameters, and the default budget of
+    /// the command line (0.333, split equally between logical errors and magic
+    /// states).
+    fn default() -> Self {
+        Self {
+            workloads: vec![],
+            parameters: vec![ModelParameters::default()],
+            budgets: vec![BudgetSplit {
+                logical: 0.333 * 0.5,
+                magic_states: 0.333 * 0.5,
+                rotations: 0.0,
+            }],
+        }
+    }
+}
+
+/// Result of one point of a sweep.
+#[derive(Clone, Debug, Serialize)]
+pub struct SweepResult {
+    /// Name of the workload.
+    pub workload: String,
+    /// Model parameters of the estimation.
+    pub parameters: ModelParameters,
+    /// Error budget of the estimation.
+    pub budget: BudgetSplit,
+    /// Estimate, if the workload could be estimated.
+    pub estimate: Option<EstimateReport>,
+    /// Reason why the workload could not be estimated.
+    pub error: Option<String>,
+}
+
+impl Sweep {
+    /// Empty sweep, see [`Sweep::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a workload to the grid.
+    pub fn with_workload(mut self, name: impl Into<String>, counts: LogicalCounts) -> Self {
+        self.workloads.push((name.into(), counts));
+        self
+    }
+
+    /// Set the model parameters of the grid.
+    pub fn with_parameters(self, parameters: Vec<ModelParameters>) -> Self {
+        Self { parameters, ..self }
+    }
+
+    /// Set the error budgets of the grid.
+    pub fn with_budgets(self, budgets: Vec<BudgetSplit>) -> Self {
+        Self { budgets, ..self }
+    }
+
+    /// Number of points of the grid.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.workloads.len() * self.parameters.len() * self.budgets.len()
+    }
+
+    /// Whether the grid has no point.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Estimate all the points of the grid on `threads` threads (or as many
+    /// as the available parallelism, if `None`).
+    ///
+    /// # Panics
+    /// If an estimation panics.
+    #[must_use]
+    pub fn run(&self, threads: Option<NonZeroUsize>) -> Vec<SweepResult> {
+        let threads = threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+            .min(self.len())
+            .max(1);
+        let next = AtomicUsize::new(0);
+
+        let mut results: Vec<Option<SweepResult>> = vec![None; self.len()];
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = vec![];
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            if index >= self.len() {
+                                break done;
+                            }
+                            done.push((index, self.estimate(index)));
+                        }
+                    })
+                })
+                .collect();
+            for worker in workers {
+                for (index, result) in worker.join().expect("estimation panicked") {
+                    results[index] = Some(result);
+                }
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every point is estimated"))
+            .collect()
+    }
+
+    /// Estimate the point `index` of the grid.
+    fn estimate(&self, index: usize) -> SweepResult {
+        let budgets = self.budgets.len();
+        let parameters = self.parameters.len();
+        let (workload, counts) = &self.workloads[index / (parameters * budgets)];
+        let model = self.parameters[(index / budgets) % parameters];
+        let budget = self.budgets[index % budgets];
+
+        let (estimate, error) = match model.estimate(counts, &budget.budget()) {
+            Ok(estimates) => (Some(estimates.report()), None),
+            Err(e) => (None, Some(e)),
+        };
+
+        SweepResult {
+            workload: workload.clone(),
+            parameters: model,
+            budget,
+            estimate,
+            error,
+        }
+    }
+}
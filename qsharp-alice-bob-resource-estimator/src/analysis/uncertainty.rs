@@ -0,0 +1,300 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Propagation of the uncertainty on the model parameters to the estimates.
+//!
+//! The parameters of [`ModelParameters`] come from experiments and fits, and
+//! carry uncertainty. Each of them can be given as a [`Distribution`] in
+//! [`UncertainParameters`] (the other ones keep their value); the workload is
+//! then estimated for many parameter sets drawn at random (Monte Carlo), and
+//! the median and a confidence interval are reported for the physical qubits,
+//! the runtime and the total error.
+//!
+//! All the parameters must be positive: the draws with a negative or zero
+//! parameter (e.g. in the tail of a normal distribution), and the ones for
+//! which no code parameter or factory reaches the error budget, are counted as
+//! failed and left out of the statistics. The intervals are thus conditional
+//! on a successful estimate, and biased toward the favourable parameters when
+//! draws fail: the propagation is an error if more than
+//! [`MAX_FAILED_FRACTION`] of the draws fail, and the report shows the number
+//! of failed draws otherwise.
+
+use std::fmt::Display;
+
+use num_traits::ToPrimitive;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use resource_estimator::estimates::ErrorBudget;
+use serde::{Deserialize, Serialize};
+
+use super::ModelParameters;
+use crate::{
+    estimates::units::{Count, Runtime},
+    LogicalCounts,
+};
+
+/// Largest fraction of failed draws for which the intervals are reported.
+pub const MAX_FAILED_FRACTION: f64 = 0.01;
+
+/// Probability distribution of a model parameter.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Distribution {
+    /// Normal distribution.
+    Normal {
+        /// Mean.
+        mean: f64,
+        /// Standard deviation.
+        std_dev: f64,
+    },
+    /// Log-normal distribution: the logarithm of the parameter is normally
+    /// distributed.
+    LogNormal {
+        /// Median of the parameter.
+        median: f64,
+        /// Standard deviation of the logarithm of the parameter.
+        sigma: f64,
+    },
+    /// Uniform distribution.
+    Uniform {
+        /// Lower bound.
+        min: f64,
+        /// Upper bound.
+        max: f64,
+    },
+}
+
+impl Distribution {
+    /// Draw a value.
+    pub fn sample(&self, rng: &mut impl Rng) -> f64 {
+        match *self {
+            Self::Normal { mean, std_dev } => mean + std_dev * standard_normal(rng),
+            Self::LogNormal { median, sigma } => median * (sigma * standard_normal(rng)).exp(),
+            Self::Uniform { min, max } => min + (max - min) * rng.gen::<f64>(),
+        }
+    }
+}
+
+/// Draw from the standard normal distribution (Box-Muller transform).
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    // 1 - u is in (0, 1], hence its logarithm is finite
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// Distributions of the model parameters; the parameters without distribution
+/// keep their value.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UncertainParameters {
+    /// Distribution of κ₁/κ₂ (which also scales the factory errors).
+    pub k1_k2: Option<Distribution>,
+    /// Distribution of κ₂, in s⁻¹.
+    pub k2: Option<Distribution>,
+    /// Distribution of the threshold of the repetition code.
+    pub threshold: Option<Distribution>,
+    /// Distribution of the prefactor of the logical phase-flip probability.
+    pub prefactor: Option<Distribution>,
+    /// Distribution of the factor applied to the duration of the logical
+    /// operations.
+    pub cycle_scale: Option<Distribution>,
+    /// Distribution of the factor applied to the error probabilities of the
+    /// factories.
+    pub factory_error_scale: Option<Distribution>,
+}
+
+impl UncertainParameters {
+    /// Draw a parameter set, with the values of `base` for the parameters
+    /// without distribution.
+    pub fn sample(&self, base: &ModelParameters, rng: &mut impl Rng) -> ModelParameters {
+        let mut draw = |distribution: Option<Distribution>, value: f64| {
+            distribution.map_or(value, |distribution| distribution.sample(rng))
+        };
+        ModelParameters {
+            k1_k2: draw(self.k1_k2, base.k1_k2),
+            k2: draw(self.k2, base.k2),
+            threshold: draw(self.threshold, base.threshold),
+            prefactor: draw(self.prefactor, base.prefactor),
+            cycle_scale: draw(self.cycle_scale, base.cycle_scale),
+            factory_error_scale: draw(self.factory_error_scale, base.factory_error_scale),
+        }
+    }
+
+    /// Estimate `counts` for `samples` parameter sets drawn with `seed`, and
+    /// summarize the results with intervals of probability `confidence` (e.g.
+    /// 0.95).
+    ///
+    /// # Errors
+    /// If `confidence` is not between 0 and 1, or if more than
+    /// [`MAX_FAILED_FRACTION`] of the draws fail.
+    pub fn propagate(
+        &self,
+        base: &ModelParameters,
+        counts: &LogicalCounts,
+        budget: &ErrorBudget,
+        samples: usize,
+        seed: u64,
+        confidence: f64,
+    ) -> Result<UncertaintyReport, String> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(format!(
+                "confidence must be between 0 and 1, got {confidence}"
+            ));
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (mut qubits, mut runtimes, mut errors) = (vec![], vec![], vec![]);
+        let mut last_failure = None;
+        for _ in 0..samples {
+            let parameters = self.sample(base, &mut rng);
+            if !parameters.is_valid() {
+                last_failure = Some(format!("invalid parameters {parameters:?}"));
+                continue;
+            }
+            match parameters.estimate(counts, budget) {
+                Ok(estimates) => {
+                    qubits.push(estimates.physical_qubits().to_f64().expect("can't convert"));
+                    runtimes.push(estimates.runtime().to_f64().expect("can't convert"));
+                    errors.push(estimates.total_error());
+                }
+                Err(e) => last_failure = Some(e),
+            }
+        }
+
+        if qubits.is_empty() {
+            return Err(format!(
+                "all the {samples} draws failed (last error: {})",
+                last_failure.unwrap_or_else(|| "no draw".into())
+            ));
+        }
+        let failed_samples = samples - qubits.len();
+        if failed_samples.to_f64().expect("can't convert")
+            > MAX_FAILED_FRACTION * samples.to_f64().expect("can't convert")
+        {
+            return Err(format!(
+                "{failed_samples} of the {samples} draws failed, the intervals would be \
+                 biased (last error: {})",
+                last_failure.unwrap_or_default()
+            ));
+        }
+
+        Ok(UncertaintyReport {
+            samples,
+            failed_samples,
+            confidence,
+            physical_qubits: Interval::new(qubits, confidence),
+            runtime_ns: Interval::new(runtimes, confidence),
+            total_error: Interval::new(errors, confidence),
+        })
+    }
+}
+
+impl ModelParameters {
+    /// Whether all the parameters are positive.
+    fn is_valid(&self) -> bool {
+        [
+            self.k1_k2,
+            self.k2,
+            self.threshold,
+            self.prefactor,
+            self.cycle_scale,
+            self.factory_error_scale,
+        ]
+        .iter()
+        .all(|&value| value > 0.0)
+    }
+}
+
+/// Median and confidence interval of a result.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Interval {
+    /// Median.
+    pub median: f64,
+    /// Lower bound of the confidence interval.
+    pub lower: f64,
+    /// Upper bound of the confidence interval.
+    pub upper: f64,
+}
+
+impl Interval {
+    /// Median and central interval of probability `confidence` of `values`
+    /// (not empty).
+    fn new(mut values: Vec<f64>, confidence: f64) -> Self {
+        values.sort_unstable_by(f64::total_cmp);
+        let quantile = |q: f64| {
+            let last = (values.len() - 1).to_f64().expect("can't convert");
+            values[(q * last).round().to_usize().expect("can't convert")]
+        };
+        let tail = (1.0 - confidence) / 2.0;
+        Self {
+            median: quantile(0.5),
+            lower: quantile(tail),
+            upper: quantile(1.0 - tail),
+        }
+    }
+}
+
+/// Statistics of the estimates over the draws of the model parameters.
+#[derive(Clone, Debug, Serialize)]
+pub struct UncertaintyReport {
+    /// Number of draws.
+    pub samples: usize,
+    /// Number of draws without estimate, left out of the intervals (at most
+    /// [`MAX_FAILED_FRACTION`] of the draws), which are thus biased toward
+    /// the favourable parameters.
+    pub failed_samples: usize,
+    /// Probability of the confidence intervals.
+    pub confidence: f64,
+    /// Number of physical qubits.
+    pub physical_qubits: Interval,
+    /// Runtime in nanoseconds.
+    pub runtime_ns: Interval,
+    /// Total error probability.
+    pub total_error: Interval,
+}
+
+impl Display for UncertaintyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let to_u64 = |value: f64| value.round().to_u64().expect("can't convert");
+        let (qubits, runtime, error) = (&self.physical_qubits, &self.runtime_ns, &self.total_error);
+
+        writeln!(f, "─────────────────────────────")?;
+        writeln!(
+            f,
+            "# draws:             {} ({} failed)",
+            self.samples, self.failed_samples
+        )?;
+        if self.failed_samples > 0 {
+            writeln!(
+                f,
+                "warning: failed draws are left out of the intervals, biased \
+                 toward favourable parameters"
+            )?;
+        }
+        writeln!(
+            f,
+            "median ({}% interval)",
+            (self.confidence * 100.0).round()
+        )?;
+        writeln!(
+            f,
+            "# physical qubits:   {} ({} – {})",
+            Count(to_u64(qubits.median)),
+            Count(to_u64(qubits.lower)),
+            Count(to_u64(qubits.upper))
+        )?;
+        writeln!(
+            f,
+            "runtime:             {} ({} – {})",
+            Runtime(to_u64(runtime.median)),
+            Runtime(to_u64(runtime.lower)),
+            Runtime(to_u64(runtime.upper))
+        )?;
+        writeln!(
+            f,
+            "total error:         {:.5} ({:.5} – {:.5})",
+            error.median, error.lower, error.upper
+        )?;
+        writeln!(f, "─────────────────────────────")
+    }
+}
@@ -11,9 +11,14 @@
 //! - code distance
 //! - average number of photons |α|²
 //!
-//! Hard-coded values:
-//! - 1/κ₂ = 100 ns (sets the gates speed)
+//! Default values, which can be changed to assess their impact (see
+//! [`crate::analysis::sensitivity`]):
 //! - (κ₁/κ₂)_th = 0.013 (obtained by circuit-level simulation)
+//! - prefactor of the logical phase-flip probability = 5.6e-2
+//!
+//! The gates speed is set by κ₂ (see [`CatQubit`]).
+//!
+//! Hard-coded values:
 //! - max distance (for iteration) = 49
 //! - max |α|² (for iteration) = 30.0
 
@@ -27,6 +32,7 @@ use crate::qubit::CatQubit;
 /// Represents a repetition code.
 pub struct RepetitionCode {
     p_threshold: f64,
+    prefactor: f64,
 }
 
 impl RepetitionCode {
@@ -36,6 +42,33 @@ impl RepetitionCode {
         Self::default()
     }
 
+    #[must_use]
+    /// Set the threshold (κ₁/κ₂)_th.
+    pub fn with_threshold(self, p_threshold: f64) -> Self {
+        Self {
+            p_threshold,
+            ..self
+        }
+    }
+
+    #[must_use]
+    /// Set the prefactor of the logical phase-flip probability.
+    pub fn with_prefactor(self, prefactor: f64) -> Self {
+        Self { prefactor, ..self }
+    }
+
+    #[must_use]
+    /// Threshold (κ₁/κ₂)_th.
+    pub fn threshold(&self) -> f64 {
+        self.p_threshold
+    }
+
+    #[must_use]
+    /// Prefactor of the logical phase-flip probability.
+    pub fn prefactor(&self) -> f64 {
+        self.prefactor
+    }
+
     #[must_use]
     /// Logical phaseflip probability per round, as given by
     /// [arXiv:2302.06639](https://arxiv.org/abs/2302.06639) (p. 28, eq. E1).
@@ -44,14 +77,12 @@ impl RepetitionCode {
         physical_qubit: &CatQubit,
         parameter: &CodeParameter,
     ) -> Option<f64> {
-        // arXiv:2302.06639 (p. 29, Fig. 26)
-        let prefactor = 5.6e-2;
         let exponent = (i32::from_u64(parameter.distance)? + 1) / 2;
 
         // Logical phase-flip error rate per cycle of the repetition code
         // arXiv:2302.06639 (p. 3, eq. 4)
         Some(
-            prefactor
+            self.prefactor
                 * ((parameter.alpha_sq.powf(0.86) * physical_qubit.k1_k2) / self.p_threshold)
                     .powi(exponent),
         )
@@ -61,7 +92,7 @@ impl RepetitionCode {
     #[must_use]
     /// Logical bitflip probability per round, as given in
     /// [arXiv:2302.06639](https://arxiv.org/abs/2302.06639) (eq. 3).
-    fn logical_bitflip_probability(parameter: &CodeParameter) -> Option<f64> {
+    pub(crate) fn logical_bitflip_probability(parameter: &CodeParameter) -> Option<f64> {
         // number of CX gates in a repetition code cycle
         let ncx = 2 * (parameter.distance - 1);
 
@@ -81,7 +112,12 @@ impl Default for RepetitionCode {
     /// can tune, but the result of a circuit-level simulation.
     fn default() -> Self {
         let p_threshold = 0.013;
-        Self { p_threshold }
+        // arXiv:2302.06639 (p. 29, Fig. 26)
+        let prefactor = 5.6e-2;
+        Self {
+            p_threshold,
+            prefactor,
+        }
     }
 }
 
@@ -99,6 +135,18 @@ impl CodeParameter {
     pub fn new(distance: u64, alpha_sq: f64) -> Self {
         Self { distance, alpha_sq }
     }
+
+    #[must_use]
+    /// Distance of the repetition code.
+    pub fn distance(&self) -> u64 {
+        self.distance
+    }
+
+    #[must_use]
+    /// Average number of photons |α|² in each cat.
+    pub fn alpha_sq(&self) -> f64 {
+        self.alpha_sq
+    }
 }
 
 impl Display for CodeParameter {
@@ -179,12 +227,15 @@ impl ErrorCorrection for RepetitionCode {
 
     fn logical_cycle_time(
         &self,
-        _qubit: &Self::Qubit,
+        qubit: &Self::Qubit,
         parameter: &Self::Parameter,
     ) -> Result<u64, String> {
         // arXiv:2302.06639 (p. 28, repetition code cycle time in d code cycles)
         // Time for one round : 5/κ₂
-        Ok(500 * parameter.distance) // ns, corresponds to 1/κ₂ = 100 ns
+        f64::from_u64(parameter.distance)
+            .map(|distance| (5.0 * qubit.time_unit() * distance).round())
+            .and_then(u64::from_f64)
+            .ok_or_else(|| "cannot compute logical cycle time".into())
     }
 
     fn logical_error_rate(
@@ -7,19 +7,106 @@
 //! Can compute logical space and time overheads for resource estimation from Q#
 //! files. Can also directly instantiate a logical count from number of logical
 //! qubits, of CX and of CCX.
+//!
+//! Counts can be composed sequentially or in parallel, see [`composition`].
+//!
+//! ### Logical ANDs
+//! A CCX whose target is a freshly allocated (or reset) qubit is counted as a
+//! compute-AND, and the measurement-based uncomputation of such a target (H,
+//! then measurement, as in the Q# standard library `ApplyAndAssuming0Target`)
+//! is counted as an uncompute-AND, as they are much cheaper than a CCX. The
+//! cost of an uncompute-AND includes the CZ correction applied on its `One`
+//! outcome with probability 1/2 (see [`cycles`]), hence the CZ that follows
+//! such an outcome is not counted again.
+//!
+//! ### Measurements
+//! Outcomes of measurements are `Zero` by default; see [`measurement`] for the
+//! other options. As the quantum state is not simulated, `qubit_is_zero` (used
+//! by assertions) always returns `true`.
+//!
+//! ### Fast counting of repeated operations
+//! Executing every gate of large arithmetic circuits is impractical. The
+//! counter honours the caching intrinsics of the Q# standard library
+//! (`Microsoft.Quantum.ResourceEstimation`):
+//! - the first time `BeginEstimateCaching(name, variant)` is called for a
+//!   given pair, it returns `true` and the operation body is executed and
+//!   counted until the matching `EndEstimateCaching()`;
+//! - the following calls with the same pair return `false`, so the body is
+//!   skipped, and the recorded cost is added instead.
+//!
+//! The `variant` is typically the size of the arguments (e.g. the register
+//! length), so that calls with the same argument shapes share their cost.
+//! `RepeatEstimates(count)` is also supported: the body is counted once and
+//! its cost multiplied by `count` (with `count = 0`, its gates and their
+//! interactions are removed, but the qubits it allocated stay reserved).
+//! Counts that overflow are an error.
+//!
+//! ### Traces
+//! The counted operations can be written to a trace with
+//! [`LogicalCounts::from_qsharp_with_trace`], and counted again later from the
+//! trace with [`LogicalCounts::from_trace`] (see [`trace`]).
+//!
+//! ### Known subroutine costs
+//! Costs taken from the literature can be injected from Q# without simulating
+//! the subroutine, by calling
+//! `AliceAndBob.ResourceEstimation.AccountForCatEstimates(cx, ccx, qubits)`,
+//! where `qubits` is the number of auxiliary qubits used by the subroutine on
+//! top of the ones already allocated. The namespace is automatically added to
+//! the sources by [`LogicalCounts::from_qsharp`].
+//!
+//! ### Qubit lifetimes
+//! The counter also records how long each qubit stays allocated, to give the
+//! actual qubit-cycle volume of the program (see [`volume`]).
+//!
+//! ### Gate durations
+//! The duration of each logical operation, in logical cycles, can be changed
+//! (see [`cycles`]).
+//!
+//! ### Connectivity
+//! The pairs of qubits interacting through two-qubit gates are recorded, to
+//! charge the routing of CX gates between distant patches (see [`placement`]).
+//! By default, the layout provides all-to-all connectivity at no extra cost.
 
-use std::{fs::read_to_string, path::Path};
+use std::{collections::HashMap, fs::read_to_string, io::Write, path::Path, sync::Arc};
 
 use num_bigint::BigUint;
 use num_complex::Complex;
 use num_traits::ToPrimitive;
 use qsc::{
-    interpret::{GenericReceiver, Interpreter},
+    interpret::{GenericReceiver, Interpreter, Value},
     Backend, LanguageFeatures, SourceMap, TargetCapabilityFlags,
 };
+use rand::{rngs::StdRng, SeedableRng};
 use resource_estimator::estimates::{ErrorBudget, Overhead};
 
-/// Count the number of logical qubits, CX and CCX gates.
+use crate::layout::{Layout, RoutingLayout};
+pub use cycles::CycleCosts;
+use measurement::Outcomes;
+pub use measurement::SampledCounts;
+pub use placement::{Connectivity, Placement};
+pub use trace::{Operation, TraceRecorder};
+pub use volume::IdleErrorModel;
+
+pub mod composition;
+pub mod cycles;
+pub mod measurement;
+pub mod placement;
+pub mod trace;
+pub mod volume;
+
+/// Q# declarations of the intrinsics specific to this counter.
+const CAT_ESTIMATES_SOURCE: &str = r"
+namespace AliceAndBob.ResourceEstimation {
+    /// Accounts for `cx` CX gates and `ccx` CCX gates, using `qubits`
+    /// auxiliary qubits on top of the ones currently allocated.
+    operation AccountForCatEstimates(cx : Int, ccx : Int, qubits : Int) : Unit {
+        body intrinsic;
+    }
+}
+";
+
+/// Count the number of logical qubits, CX and CCX gates, logical ANDs,
+/// measurements and resets.
 #[allow(clippy::struct_field_names)]
 #[derive(Clone, Default)]
 #[must_use]
@@ -27,8 +114,79 @@ pub struct LogicalCounts {
     pub(crate) qubit_count: u64,
     pub(crate) cx_count: u64,
     pub(crate) ccx_count: u64,
+    pub(crate) measurement_count: u64,
+    pub(crate) reset_count: u64,
+    pub(crate) and_compute_count: u64,
+    pub(crate) and_uncompute_count: u64,
+    // qubit-cycles during which qubits are allocated, up to `synced_cycles`;
+    // `None` if the lifetimes are unknown (all qubits are then live)
+    live_qubit_cycles: Option<f64>,
+    synced_cycles: f64,
+    idle_error_model: IdleErrorModel,
+    cycle_costs: CycleCosts,
+    interactions: HashMap<(usize, usize), u64>, // two-qubit gates by pair of qubits
+    connectivity: Connectivity,
+    placement: Option<Placement>, // `None` to place the qubits in index order
+    layout: Layout,
+
+    free_list: Vec<usize>,         // holds indices of allocated qubits
+    qubit_states: Vec<QubitState>, // indexed by qubit
+    frames: Vec<Frame>,            // cached or repeated regions being counted
+    cache: HashMap<(String, i64), Cost>,
+    outcomes: Outcomes,
+    and_correction: bool, // the next CZ corrects an uncompute-AND
+}
+
+/// Gates counted in a region of the program, and number of qubits (and
+/// qubit-cycles) allocated on top of the ones already in use when entering
+/// the region.
+#[allow(clippy::struct_field_names)]
+#[derive(Clone, Copy, Default)]
+struct Cost {
+    cx_count: u64,
+    ccx_count: u64,
+    measurement_count: u64,
+    reset_count: u64,
+    and_compute_count: u64,
+    and_uncompute_count: u64,
+    extra_qubits: u64,
+    extra_qubit_cycles: f64,
+}
 
-    free_list: Vec<usize>, // holds indices of allocated qubits
+/// What is known of a qubit, to recognize logical ANDs.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum QubitState {
+    /// In state |0⟩ (allocated or reset, and untouched since).
+    Fresh,
+    /// Target of a compute-AND, untouched since.
+    AndTarget,
+    /// Target of a compute-AND, after the H of its uncomputation.
+    Uncomputing,
+    /// Anything else.
+    #[default]
+    Used,
+}
+
+/// Kind of region being counted.
+#[derive(Clone)]
+enum FrameKind {
+    /// Operation body whose cost is stored in the cache under this key.
+    Cache((String, i64)),
+    /// Body of a `RepeatEstimates` block, executed once but counted `count`
+    /// times.
+    Repeat(u64),
+}
+
+/// Counts when entering a region, to compute its cost when leaving it.
+#[derive(Clone)]
+struct Frame {
+    kind: FrameKind,
+    start: Cost,
+    start_in_use: u64,
+    peak_in_use: u64,
+    // interactions when entering a region repeated 0 times, to remove the
+    // ones of its body
+    start_interactions: Option<HashMap<(usize, usize), u64>>,
 }
 
 impl LogicalCounts {
@@ -39,131 +197,571 @@ impl LogicalCounts {
             qubit_count,
             cx_count,
             ccx_count,
-            free_list: vec![],
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of compute-AND and uncompute-AND.
+    ///
+    /// Note that the CCX gates whose target is known to be |0⟩ should be
+    /// counted as compute-AND, and not as CCX.
+    pub fn with_logical_ands(self, and_compute_count: u64, and_uncompute_count: u64) -> Self {
+        Self {
+            and_compute_count,
+            and_uncompute_count,
+            ..self
+        }
+    }
+
+    /// Set the number of single-qubit measurements and resets.
+    pub fn with_measurements(self, measurement_count: u64, reset_count: u64) -> Self {
+        Self {
+            measurement_count,
+            reset_count,
+            ..self
         }
     }
 
+    /// Set the layout of the qubits, which gives the number of routing qubits.
+    pub fn with_layout(self, layout: impl RoutingLayout + Send + Sync + 'static) -> Self {
+        Self {
+            layout: Layout(Arc::new(layout)),
+            ..self
+        }
+    }
+
+    fn with_shared_layout(self, layout: Layout) -> Self {
+        Self { layout, ..self }
+    }
+
+    /// Layout of the qubits.
+    #[must_use]
+    pub fn layout(&self) -> &dyn RoutingLayout {
+        self.layout.0.as_ref()
+    }
+
     /// Count the logical resources from a Q# file.
+    ///
+    /// All measurements return `Zero`.
     pub fn from_qsharp(filename: impl AsRef<Path>) -> Result<Self, String> {
+        let mut interpreter = Self::qsharp_interpreter(filename)?;
+        Self::count_with(&mut interpreter, Outcomes::Zero)
+    }
+
+    /// Count the logical resources from a Q# file, `samples` times, with
+    /// measurement outcomes drawn uniformly at random.
+    ///
+    /// The execution `i` uses the seed `seed + i`, so that the results are
+    /// reproducible.
+    pub fn sample_qsharp(
+        filename: impl AsRef<Path>,
+        samples: usize,
+        seed: u64,
+    ) -> Result<SampledCounts, String> {
+        if samples == 0 {
+            return Err("At least one sample is required".into());
+        }
+        let mut interpreter = Self::qsharp_interpreter(filename)?;
+
+        let counts = (0..samples as u64)
+            .map(|i| {
+                let rng = StdRng::seed_from_u64(seed.wrapping_add(i));
+                Self::count_with(&mut interpreter, Outcomes::Random(Box::new(rng)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(SampledCounts::new(counts))
+    }
+
+    /// Count the logical resources from a Q# file, for every possible sequence
+    /// of measurement outcomes.
+    ///
+    /// Branches are explored in depth-first order, and the exploration fails
+    /// if there are more than `max_branches` of them.
+    pub fn explore_qsharp(
+        filename: impl AsRef<Path>,
+        max_branches: usize,
+    ) -> Result<SampledCounts, String> {
+        let mut interpreter = Self::qsharp_interpreter(filename)?;
+
+        let mut counts = vec![];
+        let mut prefix = Some(vec![]);
+        while let Some(branch) = prefix {
+            if counts.len() == max_branches {
+                return Err(format!("More than {max_branches} branches to explore"));
+            }
+            let mut counter = Self::count_with(
+                &mut interpreter,
+                Outcomes::Scripted {
+                    prefix: branch,
+                    history: vec![],
+                },
+            )?;
+            prefix = std::mem::take(&mut counter.outcomes).next_branch();
+            counts.push(counter);
+        }
+
+        Ok(SampledCounts::new(counts))
+    }
+
+    /// Compile a Q# file, together with the intrinsics of this counter.
+    fn qsharp_interpreter(filename: impl AsRef<Path>) -> Result<Interpreter, String> {
         let content = read_to_string(filename).map_err(|_| String::from("Cannot read filename"))?;
 
-        let sources = SourceMap::new([("source".into(), content.into())], None);
+        let sources = SourceMap::new(
+            [
+                ("source".into(), content.into()),
+                ("cat_estimates".into(), CAT_ESTIMATES_SOURCE.into()),
+            ],
+            None,
+        );
 
-        let mut interpreter = Interpreter::new(
+        Interpreter::new(
             true,
             sources,
             qsc::PackageType::Exe,
             TargetCapabilityFlags::all(),
             LanguageFeatures::default(),
         )
-        .map_err(|_| String::from("Cannot create interpreter"))?;
+        .map_err(|_| String::from("Cannot create interpreter"))
+    }
+
+    /// Count the logical resources from a Q# file, and write the trace of
+    /// the counted operations into `writer` (see [`trace`]).
+    ///
+    /// All measurements return `Zero`.
+    pub fn from_qsharp_with_trace(
+        filename: impl AsRef<Path>,
+        writer: impl Write,
+    ) -> Result<Self, String> {
+        let mut interpreter = Self::qsharp_interpreter(filename)?;
+        let mut recorder = TraceRecorder::new(writer);
+        Self::run(&mut interpreter, &mut recorder)?;
+        recorder.finish()
+    }
+
+    /// Count the logical resources of one execution of the entry point.
+    fn count_with(interpreter: &mut Interpreter, outcomes: Outcomes) -> Result<Self, String> {
+        let mut counter = Self {
+            outcomes,
+            ..Self::default()
+        };
+        Self::run(interpreter, &mut counter)?;
+        Ok(counter)
+    }
 
-        let mut counter = Self::default();
+    /// Execute the entry point with the given backend.
+    fn run(
+        interpreter: &mut Interpreter,
+        backend: &mut impl Backend<ResultType = bool>,
+    ) -> Result<(), String> {
         let mut stdout = std::io::stdout();
         let mut out = GenericReceiver::new(&mut stdout);
 
         interpreter
-            .eval_entry_with_sim(&mut counter, &mut out)
+            .eval_entry_with_sim(backend, &mut out)
             .map_err(|_| String::from("Cannot estimate Q# code"))?;
 
-        Ok(counter)
+        Ok(())
+    }
+
+    /// Number of qubits currently allocated.
+    fn qubits_in_use(&self) -> u64 {
+        self.qubit_count - self.free_list.len() as u64
+    }
+
+    /// Duration of the gates counted so far, in logical cycles.
+    pub(crate) fn cycles(&self) -> f64 {
+        self.snapshot().cycles(&self.cycle_costs)
+    }
+
+    /// Record that the qubits in use have been live since the last call, as
+    /// the number of qubits in use is about to change.
+    fn sync_live_qubit_cycles(&mut self) {
+        let cycles = self.cycles();
+        let in_use = self.qubits_in_use().to_f64().expect("can't convert");
+        *self.live_qubit_cycles.get_or_insert(0.0) += in_use * (cycles - self.synced_cycles);
+        self.synced_cycles = cycles;
+    }
+
+    /// Gate counts so far, with the number of qubits in use as `extra_qubits`
+    /// and the live qubit-cycles as `extra_qubit_cycles`.
+    fn snapshot(&self) -> Cost {
+        Cost {
+            cx_count: self.cx_count,
+            ccx_count: self.ccx_count,
+            measurement_count: self.measurement_count,
+            reset_count: self.reset_count,
+            and_compute_count: self.and_compute_count,
+            and_uncompute_count: self.and_uncompute_count,
+            extra_qubits: self.qubits_in_use(),
+            extra_qubit_cycles: self.live_qubit_cycles.unwrap_or_default(),
+        }
+    }
+
+    /// Record that `in_use` qubits are simultaneously used in the innermost
+    /// region.
+    fn update_peak(&mut self, in_use: u64) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.peak_in_use = frame.peak_in_use.max(in_use);
+        }
+    }
+
+    /// Make sure that `extra` qubits can be used on top of the ones currently
+    /// allocated, without allocating them.
+    fn reserve_qubits(&mut self, extra: u64) {
+        self.sync_live_qubit_cycles();
+        let required = self.qubits_in_use() + extra;
+        while self.qubit_count < required {
+            self.free_list
+                .push(self.qubit_count.to_usize().expect("qubit is too large"));
+            self.qubit_count += 1;
+        }
+        self.update_peak(required);
+    }
+
+    /// Add `times` the cost of a region.
+    fn account_for(&mut self, cost: &Cost, times: u64) -> Result<(), String> {
+        let add = |count: u64, cost: u64| {
+            cost.checked_mul(times)
+                .and_then(|cost| count.checked_add(cost))
+                .ok_or_else(|| format!("gate count overflow (region counted {times} times)"))
+        };
+        self.sync_live_qubit_cycles();
+        self.cx_count = add(self.cx_count, cost.cx_count)?;
+        self.ccx_count = add(self.ccx_count, cost.ccx_count)?;
+        self.measurement_count = add(self.measurement_count, cost.measurement_count)?;
+        self.reset_count = add(self.reset_count, cost.reset_count)?;
+        self.and_compute_count = add(self.and_compute_count, cost.and_compute_count)?;
+        self.and_uncompute_count = add(self.and_uncompute_count, cost.and_uncompute_count)?;
+        // the qubits in use are counted when syncing below
+        *self.live_qubit_cycles.get_or_insert(0.0) +=
+            cost.extra_qubit_cycles * times.to_f64().expect("can't convert");
+        self.reserve_qubits(cost.extra_qubits);
+        Ok(())
+    }
+
+    /// Remove the gates, live qubit-cycles and interactions counted since
+    /// `start`, e.g. the body of a region repeated 0 times. The qubits
+    /// allocated since then stay reserved.
+    fn restore(&mut self, start: &Cost, interactions: HashMap<(usize, usize), u64>) {
+        self.interactions = interactions;
+        self.cx_count = start.cx_count;
+        self.ccx_count = start.ccx_count;
+        self.measurement_count = start.measurement_count;
+        self.reset_count = start.reset_count;
+        self.and_compute_count = start.and_compute_count;
+        self.and_uncompute_count = start.and_uncompute_count;
+        self.live_qubit_cycles = Some(start.extra_qubit_cycles);
+        self.synced_cycles = self.cycles();
+    }
+
+    fn push_frame(&mut self, kind: FrameKind) {
+        self.sync_live_qubit_cycles();
+        let start_in_use = self.qubits_in_use();
+        let start_interactions =
+            matches!(kind, FrameKind::Repeat(0)).then(|| self.interactions.clone());
+        self.frames.push(Frame {
+            kind,
+            start: self.snapshot(),
+            start_in_use,
+            peak_in_use: start_in_use,
+            start_interactions,
+        });
+    }
+
+    /// Leave the innermost region, returning its kind and cost.
+    fn pop_frame(&mut self) -> Result<(FrameKind, Cost), String> {
+        let frame = self
+            .frames
+            .pop()
+            .ok_or_else(|| String::from("End of estimates region without beginning"))?;
+        self.update_peak(frame.peak_in_use);
+        self.sync_live_qubit_cycles();
+
+        let mut cost = Cost {
+            cx_count: self.cx_count - frame.start.cx_count,
+            ccx_count: self.ccx_count - frame.start.ccx_count,
+            measurement_count: self.measurement_count - frame.start.measurement_count,
+            reset_count: self.reset_count - frame.start.reset_count,
+            and_compute_count: self.and_compute_count - frame.start.and_compute_count,
+            and_uncompute_count: self.and_uncompute_count - frame.start.and_uncompute_count,
+            extra_qubits: frame.peak_in_use - frame.start_in_use,
+            extra_qubit_cycles: 0.0,
+        };
+        // live qubit-cycles of the region, without the qubits already in use
+        cost.extra_qubit_cycles = self.live_qubit_cycles.unwrap_or_default()
+            - frame.start.extra_qubit_cycles
+            - frame.start_in_use.to_f64().expect("can't convert") * cost.cycles(&self.cycle_costs);
+        Ok((frame.kind, cost))
+    }
+
+    fn qubit_state(&self, q: usize) -> QubitState {
+        self.qubit_states.get(q).copied().unwrap_or_default()
+    }
+
+    fn set_qubit_state(&mut self, q: usize, state: QubitState) {
+        if q >= self.qubit_states.len() {
+            self.qubit_states.resize(q + 1, QubitState::default());
+        }
+        self.qubit_states[q] = state;
+    }
+
+    /// Record that a gate which is not part of a logical AND acts on `q`.
+    fn touch(&mut self, q: usize) {
+        self.and_correction = false;
+        self.set_qubit_state(q, QubitState::Used);
+    }
+
+    /// Count a measurement of `q`, returning whether it uncomputes a logical
+    /// AND.
+    fn measure(&mut self, q: usize) -> bool {
+        if self.qubit_state(q) == QubitState::Uncomputing {
+            self.and_uncompute_count += 1;
+            true
+        } else {
+            self.measurement_count += 1;
+            false
+        }
+    }
+
+    /// Count an operation, and give the value returned to Q#.
+    #[allow(clippy::similar_names)]
+    fn apply(&mut self, operation: &Operation) -> Result<Value, String> {
+        match *operation {
+            Operation::Allocate { q } => {
+                let allocated = self.qubit_allocate();
+                if allocated != q {
+                    return Err(format!("qubit {allocated} allocated instead of {q}"));
+                }
+            }
+            Operation::Release { q } => self.qubit_release(q),
+            Operation::Cx { ctl, q } => self.cx(ctl, q),
+            Operation::Cy { ctl, q } => self.cy(ctl, q),
+            Operation::Cz { ctl, q } => self.cz(ctl, q),
+            Operation::Ccx { ctl0, ctl1, q } => self.ccx(ctl0, ctl1, q),
+            Operation::Swap { q0, q1 } => self.swap(q0, q1),
+            Operation::H { q } => self.h(q),
+            Operation::S { q } => self.s(q),
+            Operation::SAdj { q } => self.sadj(q),
+            Operation::X { q } => self.x(q),
+            Operation::Y { q } => self.y(q),
+            Operation::Z { q } => self.z(q),
+            Operation::M { q } => return Ok(Value::Bool(self.m(q))),
+            Operation::MResetZ { q } => return Ok(Value::Bool(self.mresetz(q))),
+            Operation::Reset { q } => self.reset(q),
+            Operation::BeginCaching { ref name, variant } => {
+                return Ok(Value::Bool(self.begin_caching(name, variant)?))
+            }
+            Operation::EndCaching => self.end_caching()?,
+            Operation::BeginRepeat { count } => self.push_frame(FrameKind::Repeat(count)),
+            Operation::EndRepeat => self.end_repeat()?,
+            Operation::Account { cx, ccx, qubits } => {
+                let mut cost = Cost {
+                    cx_count: cx,
+                    ccx_count: ccx,
+                    extra_qubits: qubits,
+                    ..Cost::default()
+                };
+                // the auxiliary qubits are live during the whole subroutine
+                cost.extra_qubit_cycles =
+                    qubits.to_f64().expect("can't convert") * cost.cycles(&self.cycle_costs);
+                self.account_for(&cost, 1)?;
+            }
+        }
+        Ok(Value::unit())
+    }
+
+    /// `BeginEstimateCaching(name, variant)`: returns whether the body must
+    /// be executed.
+    fn begin_caching(&mut self, name: &str, variant: i64) -> Result<bool, String> {
+        let key = (name.to_string(), variant);
+        if let Some(cost) = self.cache.get(&key).copied() {
+            self.account_for(&cost, 1)?;
+            Ok(false)
+        } else {
+            self.push_frame(FrameKind::Cache(key));
+            Ok(true)
+        }
+    }
+
+    /// `EndEstimateCaching()`: store the cost of the body.
+    fn end_caching(&mut self) -> Result<(), String> {
+        match self.pop_frame()? {
+            (FrameKind::Cache(key), cost) => {
+                self.cache.insert(key, cost);
+                Ok(())
+            }
+            (FrameKind::Repeat(_), _) => {
+                Err("EndEstimateCaching called inside of a RepeatEstimates block".into())
+            }
+        }
+    }
+
+    /// `EndRepeatEstimatesInternal()`: the body has been counted once, account
+    /// for the other repetitions (or remove it if it is repeated 0 times).
+    fn end_repeat(&mut self) -> Result<(), String> {
+        let start = self
+            .frames
+            .last_mut()
+            .map(|frame| (frame.start, frame.start_interactions.take()));
+        match self.pop_frame()? {
+            (FrameKind::Repeat(0), _) => {
+                let (start, interactions) = start.expect("a frame was popped");
+                self.restore(&start, interactions.unwrap_or_default());
+                Ok(())
+            }
+            (FrameKind::Repeat(count), cost) => self.account_for(&cost, count - 1),
+            (FrameKind::Cache(_), _) => {
+                Err("End of RepeatEstimates block inside of a cached operation".into())
+            }
+        }
     }
 }
 
 impl Overhead for LogicalCounts {
     /// The number of logical qubits to execute the algorithm after mapping.
     ///
-    /// This number includes "horizontal" routing qubits for the logical qubits, including the top
-    /// one (in between the compute and factory parts). It does not include the "vertical" routing
-    /// qubits (they are included only for displaying the estimates results). It does not include
-    /// qubit used to produce magic states.
+    /// This number includes "horizontal" routing qubits for the logical qubits, as given by the
+    /// layout (see [`crate::layout`]). It does not include the "vertical" routing qubits (they are
+    /// included only for displaying the estimates results). It does not include qubit used to
+    /// produce magic states.
     fn logical_qubits(&self) -> u64 {
-        let horizontal_routing_qubits = self.qubit_count.div_ceil(2) + 1;
+        let factories = self.ccx_count + self.and_compute_count > 0;
+        let horizontal_routing_qubits = self
+            .layout()
+            .horizontal_routing_qubits(self.qubit_count, factories);
 
         self.qubit_count + horizontal_routing_qubits
     }
 
-    #[allow(clippy::similar_names)]
     fn logical_depth(&self, _: &ErrorBudget) -> u64 {
-        let cx_f = self.cx_count.to_f64().expect("#CX didn't convert to f64");
-        let ccx_f = self.ccx_count.to_f64().expect("#CCX didn't convert to f64");
-
-        // arXiv:2302.06639 (p. 30, Fig. 27); measurement is counted as 0.2
-        // cycles according to open source code (5 steps in a cycle)
-        let cx_cycles = 2.2;
-
-        // arXiv:2302.06639 (p. 36, Fig. 33); the cost is approximated as 3
-        // CNOT (3 * 2.2), then 1.5 CNOT subject to measurement outcome (1.5
-        // * 2.2), and measurement (0.2)
-        let ccx_cycles = 10.1;
-
-        ((cx_f * cx_cycles) + (ccx_f * ccx_cycles))
+        (self.cycles() + self.routing_cycles())
             .ceil()
             .to_u64()
             .expect("logical depth is too large")
     }
 
+    /// One Toffoli magic state per CCX and per compute-AND.
     fn num_magic_states(&self, _: &ErrorBudget, _: usize) -> u64 {
-        self.ccx_count
+        self.ccx_count + self.and_compute_count
     }
 }
 
 impl Backend for LogicalCounts {
     type ResultType = bool;
 
-    fn ccx(&mut self, _ctl0: usize, _ctl1: usize, _q: usize) {
-        self.ccx_count += 1;
+    fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
+        self.touch(ctl0);
+        self.touch(ctl1);
+        if self.qubit_state(q) == QubitState::Fresh {
+            self.and_compute_count += 1;
+            self.set_qubit_state(q, QubitState::AndTarget);
+        } else {
+            self.ccx_count += 1;
+            self.touch(q);
+        }
     }
 
-    fn cx(&mut self, _ctl: usize, _q: usize) {
+    fn cx(&mut self, ctl: usize, q: usize) {
+        self.touch(ctl);
+        self.touch(q);
+        self.interact(ctl, q, 1);
         self.cx_count += 1;
     }
 
-    fn cy(&mut self, _ctl: usize, _q: usize) {
+    fn cy(&mut self, ctl: usize, q: usize) {
+        self.touch(ctl);
+        self.touch(q);
+        self.interact(ctl, q, 1);
         self.cx_count += 1;
     }
 
-    fn cz(&mut self, _ctl: usize, _q: usize) {
-        self.cx_count += 1;
+    fn cz(&mut self, ctl: usize, q: usize) {
+        let correction = self.and_correction;
+        self.touch(ctl);
+        self.touch(q);
+        if !correction {
+            self.interact(ctl, q, 1);
+            self.cx_count += 1;
+        }
     }
 
-    fn h(&mut self, _q: usize) {}
+    fn h(&mut self, q: usize) {
+        if self.qubit_state(q) == QubitState::AndTarget {
+            self.set_qubit_state(q, QubitState::Uncomputing);
+        } else {
+            self.touch(q);
+        }
+    }
 
-    fn m(&mut self, _q: usize) -> Self::ResultType {
-        false
+    fn m(&mut self, q: usize) -> Self::ResultType {
+        let uncompute = self.measure(q);
+        self.touch(q);
+        let outcome = self.outcomes.next();
+        self.and_correction = uncompute && outcome;
+        outcome
     }
 
-    fn mresetz(&mut self, _q: usize) -> Self::ResultType {
-        false
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        let uncompute = self.measure(q);
+        if !uncompute {
+            self.reset_count += 1;
+        }
+        self.set_qubit_state(q, QubitState::Fresh);
+        let outcome = self.outcomes.next();
+        self.and_correction = uncompute && outcome;
+        outcome
     }
 
-    fn reset(&mut self, _q: usize) {}
+    fn reset(&mut self, q: usize) {
+        self.reset_count += 1;
+        self.set_qubit_state(q, QubitState::Fresh);
+    }
 
-    fn sadj(&mut self, _q: usize) {}
+    fn sadj(&mut self, q: usize) {
+        self.touch(q);
+    }
 
-    fn s(&mut self, _q: usize) {}
+    fn s(&mut self, q: usize) {
+        self.touch(q);
+    }
 
-    fn swap(&mut self, _q0: usize, _q1: usize) {
+    fn swap(&mut self, q0: usize, q1: usize) {
+        self.touch(q0);
+        self.touch(q1);
+        self.interact(q0, q1, 3);
         self.cx_count += 3;
     }
 
-    fn x(&mut self, _q: usize) {}
+    fn x(&mut self, q: usize) {
+        self.touch(q);
+    }
 
-    fn y(&mut self, _q: usize) {}
+    fn y(&mut self, q: usize) {
+        self.touch(q);
+    }
 
-    fn z(&mut self, _q: usize) {}
+    fn z(&mut self, q: usize) {
+        self.touch(q);
+    }
 
     fn qubit_allocate(&mut self) -> usize {
-        if let Some(qubit) = self.free_list.pop() {
+        self.sync_live_qubit_cycles();
+        let qubit = if let Some(qubit) = self.free_list.pop() {
             qubit
         } else {
             let qubit = self.qubit_count;
             self.qubit_count += 1;
             qubit.to_usize().expect("qubit is too large")
-        }
+        };
+        self.update_peak(self.qubits_in_use());
+        self.set_qubit_state(qubit, QubitState::Fresh);
+        qubit
     }
 
     fn qubit_release(&mut self, q: usize) {
+        self.sync_live_qubit_cycles();
         self.free_list.push(q);
     }
 
@@ -174,4 +772,8 @@ impl Backend for LogicalCounts {
     fn qubit_is_zero(&mut self, _q: usize) -> bool {
         true
     }
+
+    fn custom_intrinsic(&mut self, name: &str, arg: Value) -> Option<Result<Value, String>> {
+        Operation::dispatch(name, &arg, |operation| self.apply(operation))
+    }
 }
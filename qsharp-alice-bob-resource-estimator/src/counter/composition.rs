@@ -0,0 +1,157 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Composition of logical counts, to assemble algorithm models from the costs
+//! of their subroutines.
+//!
+//! Qubit-count semantics:
+//! - sequential composition ([`LogicalCounts::then`], also available as `+`):
+//!   the subroutines are executed one after the other and reuse the same
+//!   qubits, hence the number of qubits is the maximum of the two;
+//! - repetition ([`LogicalCounts::repeat`], also available as `* n`): the
+//!   subroutine is executed `n` times in a row on the same qubits, hence the
+//!   number of qubits is unchanged;
+//! - parallel composition ([`LogicalCounts::parallel`]): the subroutines act
+//!   on distinct qubits at the same time, hence the numbers of qubits are
+//!   summed.
+//!
+//! In all cases the gates (and measurements) are summed, as the counter does
+//! not consider parallelism (see [`crate`]). Note that the counts obtained are
+//! an upper bound when a subroutine needs qubits only during a part of its
+//! execution; the live qubit-cycles (see [`super::volume`]) keep track of it.
+//! In a parallel composition, the qubits of each subroutine are considered
+//! live while the other one executes.
+//!
+//! The composed counts keep the idle error model, the gate durations, the
+//! connectivity and the layout of the first operand, but not the recorded
+//! interactions between qubits, as the qubits of the operands are not related.
+//!
+//! Counts that overflow are an error; the operators `+` and `*`, and [`Sum`],
+//! panic instead.
+
+use std::{
+    iter::Sum,
+    ops::{Add, Mul},
+};
+
+use num_traits::ToPrimitive;
+
+use super::LogicalCounts;
+
+impl LogicalCounts {
+    /// Sequential composition: `self` followed by `other`, on the same qubits.
+    ///
+    /// # Errors
+    /// If a count overflows.
+    pub fn then(&self, other: &Self) -> Result<Self, String> {
+        Ok(self
+            .zip_gates(
+                other,
+                self.qubit_count.max(other.qubit_count),
+                u64::checked_add,
+            )?
+            .with_live_qubit_cycles(self.live_qubit_cycles() + other.live_qubit_cycles()))
+    }
+
+    /// Sequential composition of `times` executions of `self`.
+    ///
+    /// # Errors
+    /// If a count overflows.
+    pub fn repeat(&self, times: u64) -> Result<Self, String> {
+        Ok(self
+            .zip_gates(self, self.qubit_count, |a, _| a.checked_mul(times))?
+            .with_live_qubit_cycles(
+                self.live_qubit_cycles() * times.to_f64().expect("can't convert"),
+            ))
+    }
+
+    /// Parallel composition: `self` and `other` at the same time, on distinct
+    /// qubits.
+    ///
+    /// # Errors
+    /// If a count overflows.
+    pub fn parallel(&self, other: &Self) -> Result<Self, String> {
+        let waiting =
+            |a: &Self, b: &Self| a.qubit_count.to_f64().expect("can't convert") * b.cycles();
+        let qubit_count = self
+            .qubit_count
+            .checked_add(other.qubit_count)
+            .ok_or_else(overflow)?;
+        Ok(self
+            .zip_gates(other, qubit_count, u64::checked_add)?
+            .with_live_qubit_cycles(
+                self.live_qubit_cycles()
+                    + other.live_qubit_cycles()
+                    + waiting(self, other)
+                    + waiting(other, self),
+            ))
+    }
+
+    /// Counts with `qubit_count` qubits, combining each gate count of `self`
+    /// and `other` with `op`, which gives `None` on overflow.
+    fn zip_gates(
+        &self,
+        other: &Self,
+        qubit_count: u64,
+        op: impl Fn(u64, u64) -> Option<u64>,
+    ) -> Result<Self, String> {
+        let op = |a, b| op(a, b).ok_or_else(overflow);
+        Ok(Self::new(
+            qubit_count,
+            op(self.cx_count, other.cx_count)?,
+            op(self.ccx_count, other.ccx_count)?,
+        )
+        .with_logical_ands(
+            op(self.and_compute_count, other.and_compute_count)?,
+            op(self.and_uncompute_count, other.and_uncompute_count)?,
+        )
+        .with_measurements(
+            op(self.measurement_count, other.measurement_count)?,
+            op(self.reset_count, other.reset_count)?,
+        )
+        .with_idle_error_model(self.idle_error_model)
+        .with_cycle_costs(self.cycle_costs)
+        .with_connectivity(self.connectivity)
+        .with_shared_layout(self.layout.clone()))
+    }
+}
+
+/// Error of a composition whose counts overflow.
+fn overflow() -> String {
+    "count overflow in the composition of logical counts".to_string()
+}
+
+impl Add for LogicalCounts {
+    type Output = Self;
+
+    /// Sequential composition, see [`LogicalCounts::then`].
+    ///
+    /// # Panics
+    /// If a count overflows.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.then(&rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Mul<u64> for LogicalCounts {
+    type Output = Self;
+
+    /// Repetition, see [`LogicalCounts::repeat`].
+    ///
+    /// # Panics
+    /// If a count overflows.
+    fn mul(self, rhs: u64) -> Self::Output {
+        self.repeat(rhs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Sum for LogicalCounts {
+    /// Sequential composition of all the counts, with the settings of the
+    /// first one (default counts if there is none).
+    ///
+    /// # Panics
+    /// If a count overflows.
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, counts| acc + counts).unwrap_or_default()
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Duration of the logical operations, in logical cycles.
+//!
+//! The default durations are the ones of
+//! [arXiv:2302.06639](https://arxiv.org/abs/2302.06639). They can be changed
+//! with [`LogicalCounts::with_cycle_costs`], e.g. to assess how much the
+//! estimates depend on them (see [`crate::analysis::sensitivity`]).
+
+use num_traits::ToPrimitive;
+
+use super::LogicalCounts;
+
+/// Duration of each logical operation, in logical cycles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CycleCosts {
+    /// CX gate.
+    pub cx: f64,
+    /// CCX gate.
+    pub ccx: f64,
+    /// Compute-AND.
+    pub and_compute: f64,
+    /// Uncompute-AND.
+    pub and_uncompute: f64,
+    /// Single qubit measurement.
+    pub measurement: f64,
+    /// Preparation of a qubit in a computational basis state.
+    pub reset: f64,
+}
+
+impl Default for CycleCosts {
+    fn default() -> Self {
+        Self {
+            // arXiv:2302.06639 (p. 30, Fig. 27); measurement is counted as 0.2
+            // cycles according to open source code (5 steps in a cycle)
+            cx: 2.2,
+            // arXiv:2302.06639 (p. 36, Fig. 33); the cost is approximated as 3
+            // CNOT (3 * 2.2), then 1.5 CNOT subject to measurement outcome (1.5
+            // * 2.2), and measurement (0.2)
+            ccx: 10.1,
+            // Same circuit as the CCX, but the target of the magic state is
+            // directly used as output: 2 CNOT (2 * 2.2), then 1 CNOT subject to
+            // measurement outcome (2.2), and measurement (0.2)
+            and_compute: 6.8,
+            // Measurement (0.2), then a CZ with probability 1/2 (0.5 * 2.2);
+            // the CZ executed by Q# after a `One` outcome is not counted again
+            and_uncompute: 1.3,
+            // Single logical qubit measurement, one step of a cycle as above
+            measurement: 0.2,
+            // Preparation of a logical qubit in a computational basis state,
+            // also one step of a cycle
+            reset: 0.2,
+        }
+    }
+}
+
+impl CycleCosts {
+    /// All the durations multiplied by `factor`.
+    #[must_use]
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            cx: self.cx * factor,
+            ccx: self.ccx * factor,
+            and_compute: self.and_compute * factor,
+            and_uncompute: self.and_uncompute * factor,
+            measurement: self.measurement * factor,
+            reset: self.reset * factor,
+        }
+    }
+}
+
+impl LogicalCounts {
+    /// Set the duration of the logical operations.
+    ///
+    /// The live qubit-cycles already recorded (see [`super::volume`]) are
+    /// scaled as the total duration of the gates, which assumes the qubit
+    /// lifetimes are spread evenly over the gates.
+    pub fn with_cycle_costs(self, cycle_costs: CycleCosts) -> Self {
+        let old_cycles = self.cycles();
+        let counts = Self {
+            cycle_costs,
+            ..self
+        };
+        let cycles = counts.cycles();
+        let ratio = if old_cycles > 0.0 {
+            cycles / old_cycles
+        } else {
+            1.0
+        };
+        Self {
+            live_qubit_cycles: counts.live_qubit_cycles.map(|live| live * ratio),
+            synced_cycles: counts.synced_cycles * ratio,
+            ..counts
+        }
+    }
+
+    /// Duration of the logical operations.
+    #[must_use]
+    pub fn cycle_costs(&self) -> CycleCosts {
+        self.cycle_costs
+    }
+}
+
+impl super::Cost {
+    /// Duration of the gates, in logical cycles.
+    #[allow(clippy::similar_names)]
+    pub(super) fn cycles(&self, costs: &CycleCosts) -> f64 {
+        let cx_f = self.cx_count.to_f64().expect("#CX didn't convert to f64");
+        let ccx_f = self.ccx_count.to_f64().expect("#CCX didn't convert to f64");
+        let measurement_f = self
+            .measurement_count
+            .to_f64()
+            .expect("#measurements didn't convert to f64");
+        let reset_f = self
+            .reset_count
+            .to_f64()
+            .expect("#resets didn't convert to f64");
+        let and_compute_f = self
+            .and_compute_count
+            .to_f64()
+            .expect("#compute-AND didn't convert to f64");
+        let and_uncompute_f = self
+            .and_uncompute_count
+            .to_f64()
+            .expect("#uncompute-AND didn't convert to f64");
+
+        (cx_f * costs.cx)
+            + (ccx_f * costs.ccx)
+            + (and_compute_f * costs.and_compute)
+            + (and_uncompute_f * costs.and_uncompute)
+            + (measurement_f * costs.measurement)
+            + (reset_f * costs.reset)
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Outcomes of mid-circuit measurements when counting from Q#.
+//!
+//! The counter does not simulate the quantum state, hence measurement outcomes
+//! must be chosen. By default, all measurements return `Zero`, which follows a
+//! single arbitrary branch of programs with classical feedback. Outcomes can
+//! also be drawn at random (see [`LogicalCounts::sample_qsharp`]), or all the
+//! branches can be explored (see [`LogicalCounts::explore_qsharp`]).
+//!
+//! The counts summarizing the executions ([`SampledCounts::mean`] and
+//! [`SampledCounts::worst_case`]) combine the interactions between qubits of
+//! the executions in the same way as the gate counts, so that their routing
+//! cost can be estimated (see [`super::placement`]).
+
+use std::{collections::HashSet, fmt::Display};
+
+use num_traits::ToPrimitive;
+use rand::{rngs::StdRng, Rng};
+
+use super::LogicalCounts;
+
+/// Source of the measurement outcomes during one execution.
+#[derive(Clone, Default)]
+pub(super) enum Outcomes {
+    /// Every measurement returns `Zero`.
+    #[default]
+    Zero,
+    /// Outcomes are drawn uniformly at random.
+    Random(Box<StdRng>),
+    /// The first outcomes are given, the following ones are `Zero`; all the
+    /// outcomes are recorded.
+    Scripted {
+        prefix: Vec<bool>,
+        history: Vec<bool>,
+    },
+}
+
+impl Outcomes {
+    /// Outcome of the next measurement (`true` for `One`).
+    pub(super) fn next(&mut self) -> bool {
+        match self {
+            Self::Zero => false,
+            Self::Random(rng) => rng.gen(),
+            Self::Scripted { prefix, history } => {
+                let outcome = prefix.get(history.len()).copied().unwrap_or(false);
+                history.push(outcome);
+                outcome
+            }
+        }
+    }
+
+    /// Outcomes prefix of the next branch to explore, in depth-first order, or
+    /// `None` if all the branches have been explored.
+    pub(super) fn next_branch(self) -> Option<Vec<bool>> {
+        let Self::Scripted { mut history, .. } = self else {
+            return None;
+        };
+        while history.last() == Some(&true) {
+            history.pop();
+        }
+        let last = history.last_mut()?;
+        *last = true;
+        Some(history)
+    }
+}
+
+/// Logical counts of several executions of the same program, with different
+/// measurement outcomes.
+pub struct SampledCounts {
+    samples: Vec<LogicalCounts>,
+}
+
+impl SampledCounts {
+    pub(super) fn new(samples: Vec<LogicalCounts>) -> Self {
+        assert!(!samples.is_empty(), "At least one sample is required");
+        Self { samples }
+    }
+
+    /// Counts of each execution.
+    #[must_use = "the samples are only read"]
+    pub fn samples(&self) -> &[LogicalCounts] {
+        &self.samples
+    }
+
+    /// Average counts over the executions (each count is rounded up).
+    pub fn mean(&self) -> LogicalCounts {
+        let mut counts = LogicalCounts::new(
+            self.mean_of(|c| c.qubit_count),
+            self.mean_of(|c| c.cx_count),
+            self.mean_of(|c| c.ccx_count),
+        )
+        .with_measurements(
+            self.mean_of(|c| c.measurement_count),
+            self.mean_of(|c| c.reset_count),
+        )
+        .with_logical_ands(
+            self.mean_of(|c| c.and_compute_count),
+            self.mean_of(|c| c.and_uncompute_count),
+        )
+        .with_live_qubit_cycles(
+            self.samples
+                .iter()
+                .map(LogicalCounts::live_qubit_cycles)
+                .sum::<f64>()
+                / self.samples.len().to_f64().expect("can't convert"),
+        );
+        counts.interactions = self
+            .pairs()
+            .into_iter()
+            .map(|pair| {
+                (
+                    pair,
+                    self.mean_of(|c| c.interactions.get(&pair).copied().unwrap_or_default()),
+                )
+            })
+            .collect();
+        counts
+    }
+
+    /// Maximum of each count over the executions.
+    ///
+    /// Note that the maximum are taken independently, hence the result is an
+    /// upper bound that may not correspond to a single execution.
+    pub fn worst_case(&self) -> LogicalCounts {
+        let mut counts = LogicalCounts::new(
+            self.max_of(|c| c.qubit_count),
+            self.max_of(|c| c.cx_count),
+            self.max_of(|c| c.ccx_count),
+        )
+        .with_measurements(
+            self.max_of(|c| c.measurement_count),
+            self.max_of(|c| c.reset_count),
+        )
+        .with_logical_ands(
+            self.max_of(|c| c.and_compute_count),
+            self.max_of(|c| c.and_uncompute_count),
+        )
+        .with_live_qubit_cycles(
+            self.samples
+                .iter()
+                .map(LogicalCounts::live_qubit_cycles)
+                .fold(0.0, f64::max),
+        );
+        counts.interactions = self
+            .pairs()
+            .into_iter()
+            .map(|pair| {
+                (
+                    pair,
+                    self.max_of(|c| c.interactions.get(&pair).copied().unwrap_or_default()),
+                )
+            })
+            .collect();
+        counts
+    }
+
+    /// Pairs of qubits interacting in at least one execution.
+    fn pairs(&self) -> HashSet<(usize, usize)> {
+        self.samples
+            .iter()
+            .flat_map(|c| c.interactions.keys().copied())
+            .collect()
+    }
+
+    fn mean_of(&self, count: impl Fn(&LogicalCounts) -> u64) -> u64 {
+        let sum: u128 = self.samples.iter().map(|c| u128::from(count(c))).sum();
+        sum.div_ceil(self.samples.len() as u128)
+            .to_u64()
+            .expect("mean of counts is too large")
+    }
+
+    fn max_of(&self, count: impl Fn(&LogicalCounts) -> u64) -> u64 {
+        self.samples.iter().map(count).max().unwrap_or_default()
+    }
+}
+
+impl Display for SampledCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (mean, worst) = (self.mean(), self.worst_case());
+        writeln!(f, "# executions:        {}", self.samples.len())?;
+        writeln!(f, "                     mean / worst case")?;
+        writeln!(
+            f,
+            "# logical qubits:    {} / {}",
+            mean.qubit_count, worst.qubit_count
+        )?;
+        writeln!(
+            f,
+            "# CX:                {} / {}",
+            mean.cx_count, worst.cx_count
+        )?;
+        writeln!(
+            f,
+            "# CCX:               {} / {}",
+            mean.ccx_count, worst.ccx_count
+        )?;
+        writeln!(
+            f,
+            "# compute-AND:       {} / {}",
+            mean.and_compute_count, worst.and_compute_count
+        )?;
+        writeln!(
+            f,
+            "# uncompute-AND:     {} / {}",
+            mean.and_uncompute_count, worst.and_uncompute_count
+        )?;
+        writeln!(
+            f,
+            "# measurements:      {} / {}",
+            mean.measurement_count, worst.measurement_count
+        )?;
+        writeln!(
+            f,
+            "# resets:            {} / {}",
+            mean.reset_count, worst.reset_count
+        )?;
+        write!(
+            f,
+            "avg. live qubits:    {:.1} / {:.1}",
+            mean.average_live_qubits(),
+            worst.average_live_qubits()
+        )
+    }
+}
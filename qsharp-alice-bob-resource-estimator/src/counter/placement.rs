@@ -0,0 +1,246 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Placement of the logical qubits on the layout, and cost of the CX gates
+//! depending on the distance between their patches.
+//!
+//! In the layout of [arXiv:2302.06639](https://arxiv.org/abs/2302.06639)
+//! (p. 27), the compute qubits are placed in two rows on both sides of a row
+//! of "horizontal" routing qubits, hence a column holds two compute qubits and
+//! one routing qubit (other layouts are described in [`crate::layout`]). With
+//! the "vertical" routing qubits, the layout provides all-to-all connectivity,
+//! and every CX has the same duration: this is the default
+//! [`Connectivity::AllToAll`]. The other [`Connectivity`] models charge
+//! additional cycles depending on the number of columns between the control
+//! and the target.
+//!
+//! The counter records the pairs of qubits interacting through CX, CY, CZ and
+//! SWAP gates. By default, the qubits are placed in index order (the qubit `q`
+//! is in the column `q / 2` of the standard layout); [`Placement::optimize`]
+//! gives a placement reducing the distances between the qubits that interact
+//! the most.
+//!
+//! The gates counted without being executed (replays of cached operations,
+//! repetitions, `AccountForCatEstimates`, or counts given directly) have no
+//! known qubits: they are charged the average routing cost of the recorded
+//! interactions, and no routing cost if no interaction is recorded.
+
+use std::collections::HashMap;
+
+use num_traits::ToPrimitive;
+
+use super::{Cost, CycleCosts, LogicalCounts};
+
+/// Cost of a CX depending on the distance between the patches.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Connectivity {
+    /// Routing qubits provide all-to-all connectivity, every CX has the same
+    /// duration.
+    #[default]
+    AllToAll,
+    /// The routing takes `cycles_per_column` additional cycles for each column
+    /// between the qubits beyond `free_distance`.
+    RoutingTime {
+        /// Number of columns that can be crossed without additional cost.
+        free_distance: u64,
+        /// Additional cycles for each column beyond `free_distance`.
+        cycles_per_column: f64,
+    },
+    /// CX only between qubits of the same or neighbouring columns; other
+    /// qubits are brought next to each other by a chain of SWAP gates (3 CX
+    /// each), and back.
+    Swaps,
+}
+
+impl Connectivity {
+    /// Additional cycles of a CX between qubits `distance` columns apart,
+    /// with the gate durations `cycle_costs`.
+    #[must_use]
+    pub fn routing_cycles(&self, distance: u64, cycle_costs: &CycleCosts) -> f64 {
+        match *self {
+            Self::AllToAll => 0.0,
+            Self::RoutingTime {
+                free_distance,
+                cycles_per_column,
+            } => {
+                distance
+                    .saturating_sub(free_distance)
+                    .to_f64()
+                    .expect("can't convert")
+                    * cycles_per_column
+            }
+            Self::Swaps => Cost {
+                cx_count: 2 * 3 * distance.saturating_sub(1),
+                ..Cost::default()
+            }
+            .cycles(cycle_costs),
+        }
+    }
+}
+
+/// Position of the logical qubits on the layout.
+#[derive(Clone, Debug, Default)]
+pub struct Placement {
+    slots: Vec<u64>, // indexed by qubit
+}
+
+impl Placement {
+    /// Place the qubits in the given order, filling the columns of the layout.
+    ///
+    /// The qubits which are not listed are placed after the listed ones.
+    #[must_use]
+    pub fn from_order(order: &[usize]) -> Self {
+        let mut slots = vec![u64::MAX; order.iter().max().map_or(0, |&q| q + 1)];
+        for (slot, &q) in order.iter().enumerate() {
+            slots[q] = slot as u64;
+        }
+        Self { slots }
+    }
+
+    /// Position of qubit `q` in the filling order of the layout.
+    #[must_use]
+    pub fn slot(&self, q: usize) -> u64 {
+        match self.slots.get(q) {
+            Some(&slot) if slot != u64::MAX => slot,
+            _ => (self.slots.len() + q) as u64,
+        }
+    }
+
+    /// Greedy placement of the qubits of `counts`, along a line.
+    ///
+    /// Starts from the qubit with the most interactions; then, the qubit with
+    /// the most interactions with the placed ones is added at the end of the
+    /// line (left or right) where it is closer to its partners.
+    #[must_use]
+    pub fn optimize(counts: &LogicalCounts) -> Self {
+        let mut neighbours: HashMap<usize, Vec<(usize, u64)>> = HashMap::new();
+        for (&(q0, q1), &count) in &counts.interactions {
+            neighbours.entry(q0).or_default().push((q1, count));
+            neighbours.entry(q1).or_default().push((q0, count));
+        }
+        let weight = |q: &usize| -> u64 { neighbours[q].iter().map(|&(_, count)| count).sum() };
+
+        let mut remaining: Vec<usize> = neighbours.keys().copied().collect();
+        remaining.sort_unstable();
+        // position of the placed qubits on the line, which spans `front..back`
+        let mut positions: HashMap<usize, i64> = HashMap::new();
+        let (mut front, mut back) = (0, 0);
+        // weight of the interactions with the placed qubits
+        let mut attraction: HashMap<usize, u64> = HashMap::new();
+
+        while !remaining.is_empty() {
+            let index = (0..remaining.len())
+                .max_by_key(|&i| {
+                    let q = remaining[i];
+                    let attraction = attraction.get(&q).copied().unwrap_or_default();
+                    (attraction, weight(&q), usize::MAX - q)
+                })
+                .expect("remaining qubits");
+            let q = remaining.swap_remove(index);
+
+            // distance to the placed partners when placed at each end
+            let (mut left, mut right) = (0, 0);
+            for (p, count) in &neighbours[&q] {
+                if let Some(&position) = positions.get(p) {
+                    left += count * (position - front + 1).unsigned_abs();
+                    right += count * (back - position).unsigned_abs();
+                }
+            }
+            if left < right {
+                front -= 1;
+                positions.insert(q, front);
+            } else {
+                positions.insert(q, back);
+                back += 1;
+            }
+
+            for &(p, count) in &neighbours[&q] {
+                *attraction.entry(p).or_default() += count;
+            }
+        }
+
+        let mut order: Vec<usize> = positions.keys().copied().collect();
+        order.sort_unstable_by_key(|q| positions[q]);
+        Self::from_order(&order)
+    }
+}
+
+impl LogicalCounts {
+    /// Set the cost of the CX gates depending on the distance between the
+    /// qubits.
+    pub fn with_connectivity(self, connectivity: Connectivity) -> Self {
+        Self {
+            connectivity,
+            ..self
+        }
+    }
+
+    /// Set the position of the qubits on the layout.
+    pub fn with_placement(self, placement: Placement) -> Self {
+        Self {
+            placement: Some(placement),
+            ..self
+        }
+    }
+
+    /// Place the qubits with [`Placement::optimize`].
+    pub fn with_optimized_placement(self) -> Self {
+        let placement = Placement::optimize(&self);
+        self.with_placement(placement)
+    }
+
+    /// Number of CX (and SWAP as 3 CX) between each pair of qubits, executed
+    /// when counting.
+    #[must_use]
+    pub fn interactions(&self) -> &HashMap<(usize, usize), u64> {
+        &self.interactions
+    }
+
+    /// Number of columns between qubits `q0` and `q1`.
+    #[must_use]
+    pub fn distance(&self, q0: usize, q1: usize) -> u64 {
+        let column = |q| {
+            let slot = self
+                .placement
+                .as_ref()
+                .map_or(q as u64, |placement| placement.slot(q));
+            slot / self.layout().qubits_per_column()
+        };
+        column(q0).abs_diff(column(q1))
+    }
+
+    /// Additional cycles spent routing the CX gates.
+    #[must_use]
+    pub fn routing_cycles(&self) -> f64 {
+        if self.connectivity == Connectivity::AllToAll {
+            return 0.0;
+        }
+
+        let (mut recorded, mut cycles) = (0, 0.0);
+        for (&(q0, q1), &count) in &self.interactions {
+            recorded += count;
+            cycles += count.to_f64().expect("can't convert")
+                * self
+                    .connectivity
+                    .routing_cycles(self.distance(q0, q1), &self.cycle_costs);
+        }
+        if recorded == 0 {
+            return 0.0;
+        }
+
+        // gates without known qubits are charged the average cost
+        let unplaced = self.cx_count.saturating_sub(recorded);
+        cycles
+            * (1.0
+                + unplaced.to_f64().expect("can't convert")
+                    / recorded.to_f64().expect("can't convert"))
+    }
+
+    /// Record `count` CX between `q0` and `q1`.
+    pub(super) fn interact(&mut self, q0: usize, q1: usize, count: u64) {
+        *self
+            .interactions
+            .entry((q0.min(q1), q0.max(q1)))
+            .or_default() += count;
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Traces of the operations seen by the counter.
+//!
+//! A trace is written in the JSON Lines format: one JSON object per operation,
+//! e.g. `{"op":"cx","ctl":0,"q":1}`. It contains every operation that has an
+//! influence on the counts (including the caching intrinsics), so that the
+//! counts can be recomputed from the trace with [`LogicalCounts::from_trace`],
+//! without running the Q# program again.
+
+use std::io::{BufRead, Write};
+
+use num_bigint::BigUint;
+use num_complex::Complex;
+use qsc::{interpret::Value, Backend};
+use serde::{Deserialize, Serialize};
+
+use super::LogicalCounts;
+
+/// Operation counted by [`LogicalCounts`].
+///
+/// Qubit arguments are named as in the corresponding [`Backend`] methods.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    /// Allocation of qubit `q`.
+    Allocate { q: usize },
+    /// Release of qubit `q`.
+    Release { q: usize },
+    /// Controlled-X.
+    Cx { ctl: usize, q: usize },
+    /// Controlled-Y.
+    Cy { ctl: usize, q: usize },
+    /// Controlled-Z.
+    Cz { ctl: usize, q: usize },
+    /// Toffoli gate.
+    Ccx { ctl0: usize, ctl1: usize, q: usize },
+    /// Swap of two qubits.
+    Swap { q0: usize, q1: usize },
+    /// Hadamard gate.
+    H { q: usize },
+    /// S gate.
+    S { q: usize },
+    /// Adjoint of the S gate.
+    SAdj { q: usize },
+    /// Pauli X gate.
+    X { q: usize },
+    /// Pauli Y gate.
+    Y { q: usize },
+    /// Pauli Z gate.
+    Z { q: usize },
+    /// Measurement in the computational basis.
+    M { q: usize },
+    /// Measurement in the computational basis, followed by a reset.
+    MResetZ { q: usize },
+    /// Reset to |0⟩.
+    Reset { q: usize },
+    /// `BeginEstimateCaching(name, variant)`.
+    BeginCaching { name: String, variant: i64 },
+    /// `EndEstimateCaching()`.
+    EndCaching,
+    /// Beginning of a `RepeatEstimates(count)` block.
+    BeginRepeat { count: u64 },
+    /// End of a `RepeatEstimates` block.
+    EndRepeat,
+    /// `AccountForCatEstimates(cx, ccx, qubits)`.
+    Account { cx: u64, ccx: u64, qubits: u64 },
+}
+
+impl Operation {
+    /// Operation corresponding to a call of a custom Q# intrinsic, or `None`
+    /// if the intrinsic is unknown.
+    fn from_intrinsic(name: &str, arg: &Value) -> Option<Result<Self, String>> {
+        let operation = match name {
+            "BeginEstimateCaching" => tuple_argument(arg).and_then(|args| {
+                let [name, variant] = args else {
+                    return Err("expected 2 arguments".into());
+                };
+                Ok(Self::BeginCaching {
+                    name: string_argument(name)?.to_string(),
+                    variant: int_argument(variant)?,
+                })
+            }),
+            "EndEstimateCaching" => Ok(Self::EndCaching),
+            "BeginRepeatEstimatesInternal" => {
+                count_argument(arg).map(|count| Self::BeginRepeat { count })
+            }
+            "EndRepeatEstimatesInternal" => Ok(Self::EndRepeat),
+            "AccountForCatEstimates" => tuple_argument(arg).and_then(|args| {
+                let [cx, ccx, qubits] = args else {
+                    return Err("expected 3 arguments".into());
+                };
+                Ok(Self::Account {
+                    cx: count_argument(cx)?,
+                    ccx: count_argument(ccx)?,
+                    qubits: count_argument(qubits)?,
+                })
+            }),
+            _ => return None,
+        };
+        Some(operation)
+    }
+
+    /// Call of a custom Q# intrinsic, shared by [`LogicalCounts`] and
+    /// [`TraceRecorder`] so that a trace counts as the program: the
+    /// operation of the intrinsic is given to `apply`, `GlobalPhase` has no
+    /// cost (and is not traced), and other intrinsics are unknown (`None`).
+    pub(super) fn dispatch(
+        name: &str,
+        arg: &Value,
+        apply: impl FnOnce(&Self) -> Result<Value, String>,
+    ) -> Option<Result<Value, String>> {
+        if name == "GlobalPhase" {
+            return Some(Ok(Value::unit()));
+        }
+        Some(Self::from_intrinsic(name, arg)?.and_then(|operation| apply(&operation)))
+    }
+}
+
+/// Extract the fields of a Q# tuple argument.
+fn tuple_argument(arg: &Value) -> Result<&[Value], String> {
+    match arg {
+        Value::Tuple(values) => Ok(&**values),
+        _ => Err("expected a tuple argument".into()),
+    }
+}
+
+/// Extract a Q# `Int` argument.
+fn int_argument(arg: &Value) -> Result<i64, String> {
+    match arg {
+        Value::Int(value) => Ok(*value),
+        _ => Err("expected an Int argument".into()),
+    }
+}
+
+/// Extract a non-negative Q# `Int` argument.
+fn count_argument(arg: &Value) -> Result<u64, String> {
+    let value = int_argument(arg)?;
+    u64::try_from(value).map_err(|_| format!("expected a non-negative Int, got {value}"))
+}
+
+/// Extract a Q# `String` argument.
+fn string_argument(arg: &Value) -> Result<&str, String> {
+    match arg {
+        Value::String(value) => Ok(&**value),
+        _ => Err("expected a String argument".into()),
+    }
+}
+
+/// Backend counting the logical resources like [`LogicalCounts`], while
+/// writing the trace of the operations.
+pub struct TraceRecorder<W: Write> {
+    counter: LogicalCounts,
+    writer: W,
+    error: Option<String>,
+}
+
+impl<W: Write> TraceRecorder<W> {
+    /// Record the operations into `writer`.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            counter: LogicalCounts::default(),
+            writer,
+            error: None,
+        }
+    }
+
+    /// Count `operation`, and write it in the trace.
+    fn record(&mut self, operation: &Operation) -> Result<Value, String> {
+        let value = self.counter.apply(operation)?;
+        self.write(operation);
+        Ok(value)
+    }
+
+    /// Write `operation` in the trace, keeping the first error.
+    fn write(&mut self, operation: &Operation) {
+        if self.error.is_none() {
+            if let Err(error) = serde_json::to_writer(&mut self.writer, operation)
+                .map_err(|e| e.to_string())
+                .and_then(|()| writeln!(self.writer).map_err(|e| e.to_string()))
+            {
+                self.error = Some(format!("Cannot write trace: {error}"));
+            }
+        }
+    }
+
+    /// Count and write an operation which cannot fail.
+    fn record_gate(&mut self, operation: &Operation) {
+        self.record(operation).expect("gates are always counted");
+    }
+
+    /// Finish writing the trace, and give the logical counts.
+    pub fn finish(mut self) -> Result<LogicalCounts, String> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        self.writer
+            .flush()
+            .map_err(|e| format!("Cannot write trace: {e}"))?;
+        Ok(self.counter)
+    }
+}
+
+impl<W: Write> Backend for TraceRecorder<W> {
+    type ResultType = bool;
+
+    fn ccx(&mut self, ctl0: usize, ctl1: usize, q: usize) {
+        self.record_gate(&Operation::Ccx { ctl0, ctl1, q });
+    }
+
+    fn cx(&mut self, ctl: usize, q: usize) {
+        self.record_gate(&Operation::Cx { ctl, q });
+    }
+
+    fn cy(&mut self, ctl: usize, q: usize) {
+        self.record_gate(&Operation::Cy { ctl, q });
+    }
+
+    fn cz(&mut self, ctl: usize, q: usize) {
+        self.record_gate(&Operation::Cz { ctl, q });
+    }
+
+    fn h(&mut self, q: usize) {
+        self.record_gate(&Operation::H { q });
+    }
+
+    fn m(&mut self, q: usize) -> Self::ResultType {
+        matches!(self.record(&Operation::M { q }), Ok(Value::Bool(true)))
+    }
+
+    fn mresetz(&mut self, q: usize) -> Self::ResultType {
+        matches!(
+            self.record(&Operation::MResetZ { q }),
+            Ok(Value::Bool(true))
+        )
+    }
+
+    fn reset(&mut self, q: usize) {
+        self.record_gate(&Operation::Reset { q });
+    }
+
+    fn sadj(&mut self, q: usize) {
+        self.record_gate(&Operation::SAdj { q });
+    }
+
+    fn s(&mut self, q: usize) {
+        self.record_gate(&Operation::S { q });
+    }
+
+    fn swap(&mut self, q0: usize, q1: usize) {
+        self.record_gate(&Operation::Swap { q0, q1 });
+    }
+
+    fn x(&mut self, q: usize) {
+        self.record_gate(&Operation::X { q });
+    }
+
+    fn y(&mut self, q: usize) {
+        self.record_gate(&Operation::Y { q });
+    }
+
+    fn z(&mut self, q: usize) {
+        self.record_gate(&Operation::Z { q });
+    }
+
+    fn qubit_allocate(&mut self) -> usize {
+        let q = self.counter.qubit_allocate();
+        self.write(&Operation::Allocate { q });
+        q
+    }
+
+    fn qubit_release(&mut self, q: usize) {
+        self.record_gate(&Operation::Release { q });
+    }
+
+    fn capture_quantum_state(&mut self) -> (Vec<(BigUint, Complex<f64>)>, usize) {
+        self.counter.capture_quantum_state()
+    }
+
+    fn qubit_is_zero(&mut self, q: usize) -> bool {
+        self.counter.qubit_is_zero(q)
+    }
+
+    fn custom_intrinsic(&mut self, name: &str, arg: Value) -> Option<Result<Value, String>> {
+        Operation::dispatch(name, &arg, |operation| self.record(operation))
+    }
+}
+
+impl LogicalCounts {
+    /// Count the logical resources from a trace written by a
+    /// [`TraceRecorder`].
+    pub fn from_trace(reader: impl BufRead) -> Result<Self, String> {
+        let mut counter = Self::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| format!("Cannot read trace: {e}"))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let operation: Operation = serde_json::from_str(&line)
+                .map_err(|e| format!("Invalid operation at line {}: {e}", index + 1))?;
+            counter
+                .apply(&operation)
+                .map_err(|e| format!("Cannot count operation at line {}: {e}", index + 1))?;
+        }
+        Ok(counter)
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Space-time volume of the logical qubits.
+//!
+//! The layout reserves the peak number of qubits for the whole runtime,
+//! although qubits are allocated and released throughout the program. When
+//! counting from Q#, the counter records the number of qubits in use between
+//! each allocation and release, which gives the live qubit-cycle volume of the
+//! program, and its average occupancy (live volume over peak volume). Counts
+//! given directly (e.g. with [`LogicalCounts::new`]) have all their qubits
+//! live during the whole runtime.
+//!
+//! The time unit is the logical cycle, with the gate durations of
+//! [`Overhead::logical_depth`], without the routing cycles (see
+//! [`super::placement`]).
+//!
+//! The [`IdleErrorModel`] sets which qubits accumulate logical errors when
+//! choosing the code distance and computing the total error. The code distance
+//! follows the idle error model when estimating with
+//! [`crate::ModelParameters`]; an estimation built directly with
+//! `PhysicalResourceEstimation` spreads the logical budget over all the
+//! qubit-cycles, as with [`IdleErrorModel::AllQubits`].
+
+use num_traits::ToPrimitive;
+use resource_estimator::estimates::{ErrorBudget, Overhead};
+
+use super::LogicalCounts;
+
+/// Which logical qubits accumulate logical errors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IdleErrorModel {
+    /// Every logical qubit of the layout, during the whole runtime.
+    #[default]
+    AllQubits,
+    /// The routing qubits during the whole runtime, and the compute qubits
+    /// only while they are allocated: a released qubit holds no information,
+    /// and is reset before being allocated again.
+    LiveQubits,
+}
+
+impl LogicalCounts {
+    /// Set which logical qubits accumulate logical errors.
+    pub fn with_idle_error_model(self, idle_error_model: IdleErrorModel) -> Self {
+        Self {
+            idle_error_model,
+            ..self
+        }
+    }
+
+    /// Set the live qubit-cycles, once all the gates are counted.
+    pub(super) fn with_live_qubit_cycles(self, live_qubit_cycles: f64) -> Self {
+        Self {
+            live_qubit_cycles: Some(live_qubit_cycles),
+            synced_cycles: self.cycles(),
+            ..self
+        }
+    }
+
+    /// Which logical qubits accumulate logical errors.
+    #[must_use]
+    pub fn idle_error_model(&self) -> IdleErrorModel {
+        self.idle_error_model
+    }
+
+    /// Number of qubit-cycles during which the qubits are allocated.
+    #[must_use]
+    pub fn live_qubit_cycles(&self) -> f64 {
+        match self.live_qubit_cycles {
+            Some(live) => {
+                // qubits still in use since the last allocation or release
+                let in_use = self.qubits_in_use().to_f64().expect("can't convert");
+                live + in_use * (self.cycles() - self.synced_cycles)
+            }
+            None => self.peak_qubit_cycles(),
+        }
+    }
+
+    /// Number of qubit-cycles reserved for the qubits, i.e. the peak number of
+    /// qubits during the whole runtime.
+    #[must_use]
+    pub fn peak_qubit_cycles(&self) -> f64 {
+        self.qubit_count.to_f64().expect("can't convert") * self.cycles()
+    }
+
+    /// Average number of qubits in use over the runtime.
+    #[must_use]
+    pub fn average_live_qubits(&self) -> f64 {
+        let cycles = self.cycles();
+        if cycles > 0.0 {
+            self.live_qubit_cycles() / cycles
+        } else {
+            self.qubit_count.to_f64().expect("can't convert")
+        }
+    }
+
+    /// Fraction of the reserved qubit-cycles during which the qubits are in
+    /// use, between 0 and 1.
+    #[must_use]
+    pub fn occupancy(&self) -> f64 {
+        let peak = self.peak_qubit_cycles();
+        if peak > 0.0 {
+            self.live_qubit_cycles() / peak
+        } else {
+            1.0
+        }
+    }
+
+    /// Fraction of the logical qubit-cycles of the layout (routing qubits
+    /// included) that accumulate logical errors, according to the idle error
+    /// model.
+    #[must_use]
+    pub fn error_volume_fraction(&self) -> f64 {
+        let cycles = self.cycles();
+        match self.idle_error_model {
+            IdleErrorModel::AllQubits => 1.0,
+            IdleErrorModel::LiveQubits if cycles == 0.0 => 1.0,
+            IdleErrorModel::LiveQubits => {
+                let logical_qubits = self.logical_qubits().to_f64().expect("can't convert");
+                let routing_qubits = (self.logical_qubits() - self.qubit_count)
+                    .to_f64()
+                    .expect("can't convert");
+                (routing_qubits * cycles + self.live_qubit_cycles()) / (logical_qubits * cycles)
+            }
+        }
+    }
+
+    /// Error budget to give to the estimation, such that the logical error
+    /// rate is chosen for the qubit-cycles that accumulate errors, according
+    /// to the idle error model.
+    ///
+    /// The estimation spreads the logical part of the budget over all the
+    /// logical qubit-cycles of the layout, hence it is divided by
+    /// [`LogicalCounts::error_volume_fraction`]. This is the only place where
+    /// the budget is adjusted: [`crate::ModelParameters::estimate`] and
+    /// [`crate::ModelParameters::frontier`] call it, and their estimates keep
+    /// the budget given by the user (see [`crate::AliceAndBobEstimates::budget`]).
+    #[must_use]
+    pub(crate) fn adjusted_error_budget(&self, budget: &ErrorBudget) -> ErrorBudget {
+        ErrorBudget::new(
+            budget.logical() / self.error_volume_fraction(),
+            budget.magic_states(),
+            budget.rotations(),
+        )
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Hardware-facing costs of an estimate: control lines, dilution
+//! refrigerators, heat load and energy consumption.
+//!
+//! Each cat qubit is stabilized by a two-photon pump and its buffer drive, and
+//! the ancilla cats of the repetition code are read out at each cycle, with
+//! several cats multiplexed on each readout line. The number of refrigerators
+//! is set both by the number of qubits per refrigerator and by the heat load
+//! of the lines at the mixing chamber, compared with its cooling power. The
+//! energy is consumed by the refrigerators and the room-temperature control
+//! electronics of the lines during the whole runtime.
+//!
+//! The default values of [`CryoModel`] are order-of-magnitude assumptions for
+//! architecture reviews, not measured figures: adjust them to the hardware
+//! being considered.
+
+use std::fmt::Display;
+
+use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+use crate::{estimates::units::Count, AliceAndBobEstimates};
+
+/// Assumptions on the control hardware and the refrigerators.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CryoModel {
+    /// Maximum number of cat qubits in a dilution refrigerator.
+    pub qubits_per_fridge: u64,
+    /// Two-photon pump lines for each cat qubit.
+    pub pump_lines_per_qubit: f64,
+    /// Buffer drive lines for each cat qubit.
+    pub buffer_lines_per_qubit: f64,
+    /// Fraction of the cat qubits which are read out (the ancillas of the
+    /// repetition code).
+    pub readout_fraction: f64,
+    /// Number of cat qubits multiplexed on a readout line.
+    pub qubits_per_readout_line: u64,
+    /// Heat load of a line at the mixing chamber, in W.
+    pub heat_load_per_line: f64,
+    /// Cooling power of a refrigerator at the mixing chamber, in W.
+    pub fridge_cooling_power: f64,
+    /// Electrical power of a refrigerator (compressor, pumps), in W.
+    pub fridge_power: f64,
+    /// Electrical power of the room-temperature electronics of a line, in W.
+    pub line_power: f64,
+}
+
+impl Default for CryoModel {
+    fn default() -> Self {
+        Self {
+            qubits_per_fridge: 10_000,
+            pump_lines_per_qubit: 1.0,
+            buffer_lines_per_qubit: 1.0,
+            readout_fraction: 0.5,
+            qubits_per_readout_line: 10,
+            heat_load_per_line: 1e-9,
+            fridge_cooling_power: 2e-5,
+            fridge_power: 25e3,
+            line_power: 5.0,
+        }
+    }
+}
+
+/// Hardware-facing costs of an estimate.
+#[derive(Clone, Debug, Serialize)]
+pub struct CryoEstimate {
+    /// Number of two-photon pump lines.
+    pub pump_lines: u64,
+    /// Number of buffer drive lines.
+    pub buffer_lines: u64,
+    /// Number of readout lines.
+    pub readout_lines: u64,
+    /// Number of dilution refrigerators.
+    pub fridges: u64,
+    /// Heat load of the lines at the mixing chambers, in W.
+    pub heat_load: f64,
+    /// Electrical power, in W.
+    pub power: f64,
+    /// Energy consumed during the runtime, in J.
+    pub energy: f64,
+}
+
+impl CryoEstimate {
+    /// Total number of microwave lines.
+    #[must_use]
+    pub fn lines(&self) -> u64 {
+        self.pump_lines + self.buffer_lines + self.readout_lines
+    }
+}
+
+impl CryoModel {
+    /// Compute the hardware-facing costs of `estimates`.
+    #[must_use]
+    pub fn estimate(&self, estimates: &AliceAndBobEstimates) -> CryoEstimate {
+        let qubits = estimates.physical_qubits();
+        let qubits_f = qubits.to_f64().expect("can't convert");
+        let lines = |per_qubit: f64| {
+            (qubits_f * per_qubit)
+                .ceil()
+                .to_u64()
+                .expect("number of lines is too large")
+        };
+
+        let pump_lines = lines(self.pump_lines_per_qubit);
+        let buffer_lines = lines(self.buffer_lines_per_qubit);
+        let readout_lines =
+            lines(self.readout_fraction).div_ceil(self.qubits_per_readout_line.max(1));
+        let all_lines = (pump_lines + buffer_lines + readout_lines)
+            .to_f64()
+            .expect("can't convert");
+
+        let heat_load = all_lines * self.heat_load_per_line;
+        let fridges_for_heat = (heat_load / self.fridge_cooling_power)
+            .ceil()
+            .to_u64()
+            .expect("number of refrigerators is too large");
+        let fridges = qubits
+            .div_ceil(self.qubits_per_fridge.max(1))
+            .max(fridges_for_heat)
+            .max(1);
+
+        let power = fridges.to_f64().expect("can't convert") * self.fridge_power
+            + all_lines * self.line_power;
+        let runtime = f64::from_u64(estimates.runtime()).expect("runtime is too large") / 1e9;
+
+        CryoEstimate {
+            pump_lines,
+            buffer_lines,
+            readout_lines,
+            fridges,
+            heat_load,
+            power,
+            energy: power * runtime,
+        }
+    }
+}
+
+impl Display for CryoEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "─────────────────────────────")?;
+        writeln!(
+            f,
+            "# lines:             {} ({} pumps, {} buffers, {} readout)",
+            Count(self.lines()),
+            Count(self.pump_lines),
+            Count(self.buffer_lines),
+            Count(self.readout_lines)
+        )?;
+        writeln!(f, "# refrigerators:     {}", self.fridges)?;
+        writeln!(f, "heat load:           {:.2} µW", self.heat_load * 1e6)?;
+        writeln!(f, "power:               {:.2} kW", self.power / 1e3)?;
+        writeln!(f, "energy:              {:.2} kWh", self.energy / 3.6e6)?;
+        writeln!(f, "─────────────────────────────")
+    }
+}
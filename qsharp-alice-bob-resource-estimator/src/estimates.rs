@@ -2,38 +2,99 @@
 // Licensed under the MIT License.
 
 //! Convenience structure to display resource estimation results.
+//!
+//! The results can also be serialized, see [`report`], or printed on one line
+//! with [`AliceAndBobEstimates::compact`], e.g. to print a frontier as a table.
 
 use std::{fmt::Display, ops::Deref};
 
-use num_traits::{FromPrimitive, ToPrimitive};
-use resource_estimator::estimates::{FactoryPart, Overhead, PhysicalResourceEstimationResult};
+use num_traits::ToPrimitive;
+use resource_estimator::estimates::{
+    ErrorBudget, FactoryPart, Overhead, PhysicalResourceEstimationResult,
+};
+
+use serde::Serialize;
 
 use crate::{code::RepetitionCode, counter::LogicalCounts, factories::ToffoliFactory};
 
+pub use errors::{ErrorBreakdown, ErrorContribution};
+pub use report::EstimateReport;
+use units::{Count, Runtime};
+
+pub mod errors;
+pub mod report;
+pub mod units;
+
 /// Represents a physical resources estimate for Alice & Bob's architecture.
-pub struct AliceAndBobEstimates(
-    PhysicalResourceEstimationResult<RepetitionCode, ToffoliFactory, LogicalCounts>,
-);
+pub struct AliceAndBobEstimates {
+    result: PhysicalResourceEstimationResult<RepetitionCode, ToffoliFactory, LogicalCounts>,
+    // error budget as given by the user, see `AliceAndBobEstimates::budget`
+    budget: ErrorBudget,
+}
 
 impl AliceAndBobEstimates {
+    /// Estimate `result` of an estimation for the error `budget`, whose
+    /// logical part was adjusted to the idle error model before being given
+    /// to the estimation.
+    pub(crate) fn with_budget(
+        result: PhysicalResourceEstimationResult<RepetitionCode, ToffoliFactory, LogicalCounts>,
+        budget: &ErrorBudget,
+    ) -> Self {
+        Self {
+            result,
+            budget: ErrorBudget::new(budget.logical(), budget.magic_states(), budget.rotations()),
+        }
+    }
+
+    #[must_use]
+    /// Give the error budget of the estimate, as given by the user.
+    ///
+    /// With the [`crate::IdleErrorModel::LiveQubits`] model, the logical part
+    /// of the budget given to the estimation (see
+    /// [`PhysicalResourceEstimationResult::error_budget`]) is larger, as the
+    /// estimation spreads it over all the logical qubit-cycles (see
+    /// [`crate::counter::volume`]).
+    pub fn budget(&self) -> &ErrorBudget {
+        &self.budget
+    }
+
     #[must_use]
-    /// Give a reference to the [`FactoryPart`] used in the estimate.
+    /// Give a reference to the [`FactoryPart`] used in the estimate, if any
+    /// magic state is needed.
     fn toffoli_factory_part(&self) -> Option<&FactoryPart<ToffoliFactory>> {
-        self.factory_parts()[0].as_ref()
+        self.factory_parts().first().and_then(Option::as_ref)
     }
 
     #[must_use]
     /// Count the number of physical qubits, routing qubits included.
     pub fn physical_qubits(&self) -> u64 {
-        // "Vertical" routing qubits must be added to ensure all-to-all connectivity
-        // Formula from arXiv: 2302.06639, p. 27. `logical_qubits()` include the "horizontal
-        // routing qubits", including the one between the computation qubits and factories.
-        let additional_routing_qubits = 2
-            * ((3
-                * (self.layout_overhead().logical_qubits()
-                    + self.toffoli_factory_part().map_or(0, FactoryPart::copies) * 5))
-                - 1);
-        self.0.physical_qubits() + additional_routing_qubits
+        self.result.physical_qubits() + self.vertical_routing_qubits()
+    }
+
+    /// Number of physical qubits of the "vertical" routing qubits.
+    fn vertical_routing_qubits(&self) -> u64 {
+        // "Vertical" routing qubits must be added to ensure all-to-all connectivity, as given by
+        // the layout. `logical_qubits()` include the "horizontal routing qubits".
+        self.layout_overhead().layout().vertical_routing_qubits(
+            self.layout_overhead().logical_qubits(),
+            self.toffoli_factory_part().map_or(0, FactoryPart::copies),
+        )
+    }
+
+    #[must_use]
+    /// Count the number of physical qubits of the logical compute qubits.
+    pub fn compute_qubits(&self) -> u64 {
+        self.layout_overhead().qubit_count * self.logical_patch().physical_qubits()
+    }
+
+    #[must_use]
+    /// Count the number of physical qubits used for routing, "horizontal" and
+    /// "vertical" routing qubits included.
+    pub fn routing_qubits(&self) -> u64 {
+        let horizontal_routing_qubits =
+            self.layout_overhead().logical_qubits() - self.layout_overhead().qubit_count;
+        horizontal_routing_qubits * self.logical_patch().physical_qubits()
+            + self.vertical_routing_qubits()
     }
 
     #[must_use]
@@ -53,18 +114,62 @@ impl AliceAndBobEstimates {
     pub fn total_error(&self) -> f64 {
         // Error is computed as 'logical + magic' without the cross term since it is
         // largely sub-leading here, and negative anyway
-        let logical = (self.num_cycles() * self.layout_overhead().logical_qubits())
+        self.logical_error() + self.magic_state_error()
+    }
+
+    #[must_use]
+    /// Compute the error probability from the logical qubits.
+    pub fn logical_error(&self) -> f64 {
+        // Only the fraction of the volume given by the idle error model
+        // accumulates logical errors
+        (self.num_cycles() * self.layout_overhead().logical_qubits())
+            .to_f64()
+            .expect("can't convert volume as f64")
+            * self.layout_overhead().error_volume_fraction()
+            * self.logical_patch().logical_error_rate()
+    }
+
+    #[must_use]
+    /// Compute the part of the logical error probability due to logical
+    /// bit-flips.
+    pub fn logical_bitflip_error(&self) -> f64 {
+        let parameter = self.logical_patch().code_parameter();
+        // arXiv:2302.06639 (p. 4, eq. 3), see `RepetitionCode::logical_error_rate`
+        let bitflip_rate = RepetitionCode::logical_bitflip_probability(parameter)
+            .expect("can't compute logical bit-flip probability")
+            * parameter
+                .distance()
+                .to_f64()
+                .expect("can't convert distance");
+        (self.num_cycles() * self.layout_overhead().logical_qubits())
             .to_f64()
             .expect("can't convert volume as f64")
-            * self.logical_patch().logical_error_rate();
-        let magic_states = self.toffoli_factory_part().map_or(0.0, |p| {
+            * self.layout_overhead().error_volume_fraction()
+            * bitflip_rate
+    }
+
+    #[must_use]
+    /// Compute the part of the logical error probability due to logical
+    /// phase-flips.
+    pub fn logical_phaseflip_error(&self) -> f64 {
+        self.logical_error() - self.logical_bitflip_error()
+    }
+
+    #[must_use]
+    /// Compute the error probability from the magic states preparations.
+    pub fn magic_state_error(&self) -> f64 {
+        self.toffoli_factory_part().map_or(0.0, |p| {
             self.num_magic_states(0)
                 .to_f64()
                 .expect("can't convert number of magic states as f64")
                 * p.factory().error_probability()
-        });
+        })
+    }
+}
 
-        logical + magic_states
+impl Serialize for AliceAndBobEstimates {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.report().serialize(serializer)
     }
 }
 
@@ -72,32 +177,56 @@ impl Deref for AliceAndBobEstimates {
     type Target = PhysicalResourceEstimationResult<RepetitionCode, ToffoliFactory, LogicalCounts>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.result
     }
 }
 
 impl From<PhysicalResourceEstimationResult<RepetitionCode, ToffoliFactory, LogicalCounts>>
     for AliceAndBobEstimates
 {
+    /// Estimate of an estimation whose error budget was not adjusted to the
+    /// idle error model.
     fn from(
         value: PhysicalResourceEstimationResult<RepetitionCode, ToffoliFactory, LogicalCounts>,
     ) -> Self {
-        Self(value)
+        let budget = value.error_budget();
+        let budget = ErrorBudget::new(budget.logical(), budget.magic_states(), budget.rotations());
+        Self {
+            result: value,
+            budget,
+        }
     }
 }
 
 impl Display for AliceAndBobEstimates {
     /// Print the final estimates.
+    ///
+    /// The precision (e.g. `{:.3}`) sets the number of decimals of the
+    /// physical qubits and runtime, see [`units`].
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+
         writeln!(f,)?;
         writeln!(f, "─────────────────────────────")?;
-        writeln!(f, "# physical qubits:    {}", self.physical_qubits())?;
         writeln!(
             f,
-            "runtime:             {:.2} hrs",
-            f64::from_u64(self.runtime()).expect("runtime is too large") / 1e9 / 3600.0
+            "# physical qubits:    {:.precision$}",
+            Count(self.physical_qubits())
+        )?;
+        writeln!(
+            f,
+            "runtime:             {:.precision$}",
+            Runtime(self.runtime())
         )?;
         writeln!(f, "total error:         {:.5}", self.total_error())?;
+        let errors = self.error_breakdown();
+        if errors.exceeds_budget {
+            writeln!(
+                f,
+                "WARNING: total error exceeds the error budget ({:.5})",
+                errors.total.budget
+            )?;
+        }
         writeln!(f, "─────────────────────────────")?;
         writeln!(
             f,
@@ -109,14 +238,83 @@ impl Display for AliceAndBobEstimates {
             "#factories:          {}",
             self.toffoli_factory_part().map_or(0, FactoryPart::copies)
         )?;
+        match self.toffoli_factory_part() {
+            Some(part) => writeln!(f, "factories distance:  {}", part.factory())?,
+            None => writeln!(f, "factories distance:  - (no magic state)")?,
+        }
+        writeln!(f, "factory fraction:    {:.2}%", self.factory_fraction())?;
+        writeln!(f, "─────────────────────────────")?;
+        write!(f, "{errors}")?;
+        writeln!(f, "─────────────────────────────")?;
         writeln!(
             f,
-            "factories distance:  {}",
-            self.toffoli_factory_part()
-                .expect("No factory part")
-                .factory()
+            "# compute-AND:       {}",
+            self.layout_overhead().and_compute_count
+        )?;
+        writeln!(
+            f,
+            "# uncompute-AND:     {}",
+            self.layout_overhead().and_uncompute_count
+        )?;
+        writeln!(
+            f,
+            "# measurements:      {}",
+            self.layout_overhead().measurement_count
+        )?;
+        writeln!(
+            f,
+            "# resets:            {}",
+            self.layout_overhead().reset_count
+        )?;
+        writeln!(
+            f,
+            "avg. live qubits:    {:.1} ({:.2}% occupancy)",
+            self.layout_overhead().average_live_qubits(),
+            self.layout_overhead().occupancy() * 100.0
         )?;
-        writeln!(f, "factory fraction:    {:.2}%", self.factory_fraction())?;
         writeln!(f, "─────────────────────────────")
     }
 }
+
+/// One-line summary of an [`AliceAndBobEstimates`], see
+/// [`AliceAndBobEstimates::compact`].
+pub struct CompactEstimates<'a>(&'a AliceAndBobEstimates);
+
+impl AliceAndBobEstimates {
+    #[must_use]
+    /// Give a one-line summary of the estimates, whose columns are described
+    /// by [`CompactEstimates::HEADER`].
+    pub fn compact(&self) -> CompactEstimates<'_> {
+        CompactEstimates(self)
+    }
+}
+
+impl CompactEstimates<'_> {
+    /// Header of the columns of the one-line summaries.
+    pub const HEADER: &'static str =
+        "   qubits     runtime      error    d   |ɑ|²  factories  fraction";
+}
+
+impl Display for CompactEstimates<'_> {
+    /// Print the one-line summary; the precision (e.g. `{:.3}`) sets the
+    /// number of decimals of the physical qubits and runtime.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let precision = f.precision().unwrap_or(2);
+        let estimates = self.0;
+        let parameter = estimates.logical_patch().code_parameter();
+
+        write!(
+            f,
+            "{:>9.precision$}  {:>10.precision$}  {:>9.2e}  {:>3}  {:>5.2}  {:>9}  {:>7.2}%",
+            Count(estimates.physical_qubits()),
+            Runtime(estimates.runtime()),
+            estimates.total_error(),
+            parameter.distance(),
+            parameter.alpha_sq(),
+            estimates
+                .toffoli_factory_part()
+                .map_or(0, FactoryPart::copies),
+            estimates.factory_fraction()
+        )
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Contributions to the total error probability, compared with the error
+//! budget.
+//!
+//! The logical error is split between logical phase-flips and bit-flips (see
+//! [`crate::code`]); both share the logical part of the budget. The errors are
+//! compared with the budget given by the user (see
+//! [`AliceAndBobEstimates::budget`]), not with the one given to the
+//! estimation, which is adjusted to the idle error model. Rotations are not
+//! used by this architecture, hence their error is always zero.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use super::AliceAndBobEstimates;
+
+/// Error probability from one source, and its share of the error budget.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct ErrorContribution {
+    /// Error probability.
+    pub error: f64,
+    /// Share of the error budget.
+    pub budget: f64,
+}
+
+impl ErrorContribution {
+    /// Whether the error is larger than its share of the budget.
+    #[must_use]
+    pub fn exceeds_budget(&self) -> bool {
+        self.error > self.budget
+    }
+}
+
+/// Contributions to the total error probability.
+#[derive(Clone, Debug, Serialize)]
+pub struct ErrorBreakdown {
+    /// Logical phase-flip error probability.
+    pub logical_phaseflip: f64,
+    /// Logical bit-flip error probability.
+    pub logical_bitflip: f64,
+    /// Logical error probability (phase-flips and bit-flips).
+    pub logical: ErrorContribution,
+    /// Error probability from the magic states preparations.
+    pub magic_states: ErrorContribution,
+    /// Error probability from the rotations.
+    pub rotations: ErrorContribution,
+    /// Total error probability.
+    pub total: ErrorContribution,
+    /// Whether the total error is larger than the total budget.
+    pub exceeds_budget: bool,
+}
+
+impl AliceAndBobEstimates {
+    #[must_use]
+    /// Split the total error probability by source.
+    pub fn error_breakdown(&self) -> ErrorBreakdown {
+        let budget = self.budget();
+        let logical = ErrorContribution {
+            error: self.logical_error(),
+            budget: budget.logical(),
+        };
+        let magic_states = ErrorContribution {
+            error: self.magic_state_error(),
+            budget: budget.magic_states(),
+        };
+        let rotations = ErrorContribution {
+            error: 0.0,
+            budget: budget.rotations(),
+        };
+        let total = ErrorContribution {
+            error: self.total_error(),
+            budget: logical.budget + magic_states.budget + rotations.budget,
+        };
+
+        ErrorBreakdown {
+            logical_phaseflip: self.logical_phaseflip_error(),
+            logical_bitflip: self.logical_bitflip_error(),
+            logical,
+            magic_states,
+            rotations,
+            total,
+            exceeds_budget: total.exceeds_budget(),
+        }
+    }
+}
+
+impl Display for ErrorBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let contribution =
+            |f: &mut std::fmt::Formatter<'_>, name: &str, contribution: &ErrorContribution| {
+                let flag = if contribution.exceeds_budget() {
+                    ", over budget"
+                } else {
+                    ""
+                };
+                writeln!(
+                    f,
+                    "{name:<21}{:.3e} (budget {:.3e}{flag})",
+                    contribution.error, contribution.budget
+                )
+            };
+
+        writeln!(f, "phase-flip error:    {:.3e}", self.logical_phaseflip)?;
+        writeln!(f, "bit-flip error:      {:.3e}", self.logical_bitflip)?;
+        contribution(f, "logical error:", &self.logical)?;
+        contribution(f, "magic state error:", &self.magic_states)?;
+        contribution(f, "rotation error:", &self.rotations)
+    }
+}
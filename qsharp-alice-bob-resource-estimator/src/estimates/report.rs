@@ -0,0 +1,183 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Machine-readable breakdown of the resource estimation results.
+//!
+//! [`EstimateReport`] holds plain values only, so that it can be serialized
+//! (e.g. to JSON with `serde_json`), stored, or sent to other threads.
+//! [`AliceAndBobEstimates`] is serialized as its report.
+
+use resource_estimator::estimates::Overhead;
+use serde::Serialize;
+
+use super::{AliceAndBobEstimates, ErrorBreakdown};
+use crate::counter::LogicalCounts;
+
+/// Breakdown of a resource estimate.
+#[derive(Clone, Debug, Serialize)]
+pub struct EstimateReport {
+    /// Name of the layout of the qubits.
+    pub layout: String,
+    /// Physical qubits, by part of the processor.
+    pub physical_qubits: QubitsReport,
+    /// Runtime in nanoseconds.
+    pub runtime_ns: u64,
+    /// Number of logical cycles.
+    pub logical_cycles: u64,
+    /// Duration of a logical cycle in nanoseconds.
+    pub logical_cycle_time_ns: u64,
+    /// Error probabilities, by source.
+    pub errors: ErrorBreakdown,
+    /// Parameters of the repetition code of the compute qubits.
+    pub code: CodeReport,
+    /// Magic state factories, if any magic state is needed.
+    pub factory: Option<FactoryReport>,
+    /// Percentage of physical qubits used by the factories.
+    pub factory_fraction: f64,
+    /// Logical counts given to the estimation.
+    pub counts: CountsReport,
+    /// Error budget, as given by the user.
+    pub budget: BudgetReport,
+}
+
+/// Physical qubits, by part of the processor.
+#[derive(Clone, Debug, Serialize)]
+pub struct QubitsReport {
+    /// All the physical qubits.
+    pub total: u64,
+    /// Qubits of the logical compute qubits.
+    pub compute: u64,
+    /// Qubits of the routing qubits, "horizontal" and "vertical".
+    pub routing: u64,
+    /// Qubits of the magic state factories.
+    pub factories: u64,
+}
+
+/// Parameters of a repetition code.
+#[derive(Clone, Debug, Serialize)]
+pub struct CodeReport {
+    /// Code distance.
+    pub distance: u64,
+    /// Average number of photons |α|² in each cat.
+    pub alpha_sq: f64,
+    /// Physical qubits of a logical qubit.
+    pub physical_qubits: u64,
+    /// Logical error rate per logical qubit and per logical cycle.
+    pub logical_error_rate: f64,
+}
+
+/// Magic state factories.
+#[derive(Clone, Debug, Serialize)]
+pub struct FactoryReport {
+    /// Code distance of the factory.
+    pub distance: usize,
+    /// Average number of photons |α|² in each cat of the factory.
+    pub alpha_sq: f64,
+    /// Error probability of a magic state.
+    pub error_probability: f64,
+    /// Acceptance probability of the magic state preparation.
+    pub acceptance_probability: f64,
+    /// Number of steps of the magic state preparation.
+    pub steps: usize,
+    /// Number of factories.
+    pub copies: u64,
+    /// Number of runs of each factory.
+    pub runs: u64,
+}
+
+/// Logical counts.
+#[derive(Clone, Debug, Serialize)]
+pub struct CountsReport {
+    /// Number of logical compute qubits.
+    pub qubits: u64,
+    /// Number of logical qubits, routing qubits included.
+    pub logical_qubits: u64,
+    /// Number of CX.
+    pub cx: u64,
+    /// Number of CCX.
+    pub ccx: u64,
+    /// Number of compute-AND.
+    pub and_compute: u64,
+    /// Number of uncompute-AND.
+    pub and_uncompute: u64,
+    /// Number of measurements.
+    pub measurements: u64,
+    /// Number of resets.
+    pub resets: u64,
+    /// Number of magic states.
+    pub magic_states: u64,
+    /// Average number of compute qubits in use.
+    pub average_live_qubits: f64,
+}
+
+/// Error budget.
+#[derive(Clone, Debug, Serialize)]
+pub struct BudgetReport {
+    /// Budget for the logical errors.
+    pub logical: f64,
+    /// Budget for the magic states errors.
+    pub magic_states: f64,
+    /// Budget for the rotations errors.
+    pub rotations: f64,
+    /// Fraction of the logical qubit-cycles that accumulate errors with the
+    /// idle error model: the logical budget given to the estimation is
+    /// `logical / error_volume_fraction`.
+    pub error_volume_fraction: f64,
+}
+
+impl AliceAndBobEstimates {
+    #[must_use]
+    /// Give the breakdown of the estimate.
+    pub fn report(&self) -> EstimateReport {
+        let counts: &LogicalCounts = self.layout_overhead();
+        let patch = self.logical_patch();
+
+        EstimateReport {
+            layout: counts.layout().name().to_string(),
+            physical_qubits: QubitsReport {
+                total: self.physical_qubits(),
+                compute: self.compute_qubits(),
+                routing: self.routing_qubits(),
+                factories: self.physical_qubits_for_factories(),
+            },
+            runtime_ns: self.runtime(),
+            logical_cycles: self.num_cycles(),
+            logical_cycle_time_ns: patch.logical_cycle_time(),
+            errors: self.error_breakdown(),
+            code: CodeReport {
+                distance: patch.code_parameter().distance(),
+                alpha_sq: patch.code_parameter().alpha_sq(),
+                physical_qubits: patch.physical_qubits(),
+                logical_error_rate: patch.logical_error_rate(),
+            },
+            factory: self.toffoli_factory_part().map(|part| FactoryReport {
+                distance: part.factory().code_distance(),
+                alpha_sq: part.factory().alpha_sq(),
+                error_probability: part.factory().error_probability(),
+                acceptance_probability: part.factory().acceptance_probability(),
+                steps: part.factory().steps(),
+                copies: part.copies(),
+                runs: part.runs(),
+            }),
+            factory_fraction: self.factory_fraction(),
+            counts: CountsReport {
+                qubits: counts.qubit_count,
+                logical_qubits: counts.logical_qubits(),
+                cx: counts.cx_count,
+                ccx: counts.ccx_count,
+                and_compute: counts.and_compute_count,
+                and_uncompute: counts.and_uncompute_count,
+                measurements: counts.measurement_count,
+                resets: counts.reset_count,
+                magic_states: self.num_magic_states(0),
+                average_live_qubits: counts.average_live_qubits(),
+            },
+            budget: BudgetReport {
+                logical: self.budget().logical(),
+                magic_states: self.budget().magic_states(),
+                rotations: self.budget().rotations(),
+                error_volume_fraction: counts.error_volume_fraction(),
+            },
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Human-readable quantities, with automatically scaled units.
+//!
+//! The precision of the formatter (e.g. `{:.3}`) sets the number of decimals,
+//! 2 by default, and its width and alignment apply to the whole quantity (e.g.
+//! `{:>10}`). The output does not depend on the locale: the decimal separator
+//! is always a dot.
+
+use std::fmt::{Alignment, Display};
+
+use num_traits::ToPrimitive;
+
+/// Duration in nanoseconds, displayed from nanoseconds to days.
+#[derive(Clone, Copy, Debug)]
+pub struct Runtime(pub u64);
+
+/// Number of items (e.g. qubits), displayed with a k, M or G suffix above
+/// one thousand.
+#[derive(Clone, Copy, Debug)]
+pub struct Count(pub u64);
+
+impl Display for Runtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [(f64, &str); 6] = [
+            (1e3, "µs"),
+            (1e6, "ms"),
+            (1e9, "s"),
+            (60e9, "min"),
+            (3600e9, "hrs"),
+            (86400e9, "days"),
+        ];
+
+        let ns = self.0.to_f64().expect("runtime is too large");
+        let precision = f.precision().unwrap_or(2);
+        let text = match scaled(ns, &UNITS, precision) {
+            Some((value, unit)) => format!("{value} {unit}"),
+            None => format!("{} ns", self.0),
+        };
+        pad(f, &text)
+    }
+}
+
+impl Display for Count {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const SUFFIXES: [(f64, &str); 3] = [(1e3, "k"), (1e6, "M"), (1e9, "G")];
+
+        let count = self.0.to_f64().expect("count is too large");
+        let precision = f.precision().unwrap_or(2);
+        let text = match scaled(count, &SUFFIXES, precision) {
+            Some((value, suffix)) => format!("{value}{suffix}"),
+            None => self.0.to_string(),
+        };
+        pad(f, &text)
+    }
+}
+
+/// `value` in the largest of `units` (sorted by scale) it reaches, rounded to
+/// `precision` decimals, or `None` if it is below the first unit.
+///
+/// The value is rounded before choosing the unit, so that it is never printed
+/// as the scale of the next unit (e.g. 999,999 is "1.00M", not "1000.00k").
+fn scaled<'a>(value: f64, units: &[(f64, &'a str)], precision: usize) -> Option<(String, &'a str)> {
+    let index = units.iter().rposition(|&(scale, _)| value >= scale)?;
+    let round = |scale: f64| format!("{:.precision$}", value / scale);
+    let (scale, unit) = units[index];
+    let rounded = round(scale);
+    match units.get(index + 1) {
+        Some(&(next, next_unit))
+            if rounded.parse::<f64>().expect("formatted number") >= next / scale =>
+        {
+            Some((round(next), next_unit))
+        }
+        _ => Some((rounded, unit)),
+    }
+}
+
+/// Write `text` with the width and alignment of the formatter (numbers are
+/// right-aligned by default), but not its precision.
+fn pad(f: &mut std::fmt::Formatter<'_>, text: &str) -> std::fmt::Result {
+    let Some(width) = f.width() else {
+        return f.write_str(text);
+    };
+    match f.align() {
+        Some(Alignment::Left) => write!(f, "{text:<width$}"),
+        Some(Alignment::Center) => write!(f, "{text:^width$}"),
+        _ => write!(f, "{text:>width$}"),
+    }
+}
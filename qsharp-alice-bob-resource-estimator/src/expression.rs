@@ -0,0 +1,543 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Symbolic cost expressions, parameterized by the problem size.
+//!
+//! Algorithm costs are often given as formulas in some parameters, e.g.
+//! `ceil(448 * n^3 / w)` CX gates for the elliptic curve discrete logarithm
+//! with `n` the key size in bits and `w` the window size
+//! ([arXiv:2302.06639](https://arxiv.org/abs/2302.06639), app C.10). A
+//! [`CostModel`] gathers such formulas for the logical qubits and gates; it can
+//! be serialized (expressions are written as strings), and evaluated for many
+//! parameter values to obtain [`LogicalCounts`] for the estimator.
+//!
+//! Syntax of the expressions:
+//! - numbers (`448`, `0.5`, `1e-3`) and parameter names (`n`, `window_size`);
+//! - operators `+`, `-`, `*`, `/` and `^` (power), with the usual precedence,
+//!   and parentheses;
+//! - functions `log2`, `ln`, `sqrt`, `ceil` and `floor`.
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::LogicalCounts;
+
+/// Symbolic expression of a cost.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CostExpression {
+    /// Constant.
+    Number(f64),
+    /// Named parameter.
+    Parameter(String),
+    /// Opposite of an expression.
+    Neg(Box<CostExpression>),
+    /// Binary operation.
+    Binary(BinaryOp, Box<CostExpression>, Box<CostExpression>),
+    /// Function applied to an expression.
+    Function(Function, Box<CostExpression>),
+}
+
+/// Binary operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// `+`
+    Add,
+    /// `-`
+    Sub,
+    /// `*`
+    Mul,
+    /// `/`
+    Div,
+    /// `^`
+    Pow,
+}
+
+/// Functions of one argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+    /// Base 2 logarithm.
+    Log2,
+    /// Natural logarithm.
+    Ln,
+    /// Square root.
+    Sqrt,
+    /// Smallest integer greater than or equal to the argument.
+    Ceil,
+    /// Largest integer less than or equal to the argument.
+    Floor,
+}
+
+impl Function {
+    const ALL: [Self; 5] = [Self::Log2, Self::Ln, Self::Sqrt, Self::Ceil, Self::Floor];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Log2 => "log2",
+            Self::Ln => "ln",
+            Self::Sqrt => "sqrt",
+            Self::Ceil => "ceil",
+            Self::Floor => "floor",
+        }
+    }
+
+    fn apply(self, x: f64) -> f64 {
+        match self {
+            Self::Log2 => x.log2(),
+            Self::Ln => x.ln(),
+            Self::Sqrt => x.sqrt(),
+            Self::Ceil => x.ceil(),
+            Self::Floor => x.floor(),
+        }
+    }
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Pow => "^",
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Self::Add | Self::Sub => 1,
+            Self::Mul | Self::Div => 2,
+            Self::Pow => 4,
+        }
+    }
+
+    fn apply(self, x: f64, y: f64) -> f64 {
+        match self {
+            Self::Add => x + y,
+            Self::Sub => x - y,
+            Self::Mul => x * y,
+            Self::Div => x / y,
+            Self::Pow => x.powf(y),
+        }
+    }
+}
+
+impl CostExpression {
+    /// Value of the expression for the given parameter values.
+    pub fn evaluate(&self, parameters: &HashMap<String, f64>) -> Result<f64, String> {
+        Ok(match self {
+            Self::Number(value) => *value,
+            Self::Parameter(name) => *parameters
+                .get(name)
+                .ok_or_else(|| format!("No value for parameter '{name}'"))?,
+            Self::Neg(expr) => -expr.evaluate(parameters)?,
+            Self::Binary(op, lhs, rhs) => {
+                op.apply(lhs.evaluate(parameters)?, rhs.evaluate(parameters)?)
+            }
+            Self::Function(function, expr) => function.apply(expr.evaluate(parameters)?),
+        })
+    }
+
+    /// Value of the expression as a count, rounded up.
+    pub fn evaluate_count(&self, parameters: &HashMap<String, f64>) -> Result<u64, String> {
+        let value = self.evaluate(parameters)?;
+        if value.is_finite() && value >= 0.0 {
+            value
+                .ceil()
+                .to_u64()
+                .ok_or_else(|| format!("Count '{self}' is too large"))
+        } else {
+            Err(format!("Count '{self}' evaluates to {value}"))
+        }
+    }
+
+    /// Names of the parameters used in the expression.
+    #[must_use]
+    pub fn parameters(&self) -> Vec<&str> {
+        let mut names = vec![];
+        self.collect_parameters(&mut names);
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    fn collect_parameters<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Self::Number(_) => {}
+            Self::Parameter(name) => names.push(name),
+            Self::Neg(expr) | Self::Function(_, expr) => expr.collect_parameters(names),
+            Self::Binary(_, lhs, rhs) => {
+                lhs.collect_parameters(names);
+                rhs.collect_parameters(names);
+            }
+        }
+    }
+
+    /// Precedence of the top-level operation, to place parentheses.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Binary(op, _, _) => op.precedence(),
+            // a negative number is printed, and parsed back, as a negation
+            Self::Neg(_) => 3,
+            Self::Number(value) if value.is_sign_negative() => 3,
+            Self::Number(_) | Self::Parameter(_) | Self::Function(_, _) => 5,
+        }
+    }
+
+    /// Write `self` as an operand, with parentheses if its precedence is
+    /// lower than (or equal to, if `strict`) `precedence`.
+    fn fmt_operand(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        precedence: u8,
+        strict: bool,
+    ) -> std::fmt::Result {
+        if self.precedence() < precedence || (strict && self.precedence() == precedence) {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+
+impl Display for CostExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Parameter(name) => write!(f, "{name}"),
+            Self::Neg(expr) => {
+                write!(f, "-")?;
+                expr.fmt_operand(f, self.precedence(), false)
+            }
+            Self::Binary(op, lhs, rhs) => {
+                // `^` is right-associative, the other operators are
+                // left-associative
+                let right_associative = *op == BinaryOp::Pow;
+                lhs.fmt_operand(f, op.precedence(), right_associative)?;
+                if right_associative {
+                    write!(f, "{}", op.symbol())?;
+                } else {
+                    write!(f, " {} ", op.symbol())?;
+                }
+                rhs.fmt_operand(f, op.precedence(), !right_associative)
+            }
+            Self::Function(function, expr) => write!(f, "{}({expr})", function.name()),
+        }
+    }
+}
+
+impl From<CostExpression> for String {
+    fn from(value: CostExpression) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for CostExpression {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<f64> for CostExpression {
+    type Error = String;
+
+    /// Constant expression; infinite and NaN values are rejected, as they
+    /// could not be written back as a number.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.is_finite() {
+            Ok(Self::Number(value))
+        } else {
+            Err(format!("Invalid constant {value}"))
+        }
+    }
+}
+
+impl FromStr for CostExpression {
+    type Err = String;
+
+    /// Parse an expression; errors give the column (from 1) of the offending
+    /// character.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s).map_err(|e| format!("{e} in '{s}'"))?,
+            position: 0,
+            end: s.chars().count(),
+        };
+        let expr = parser.sum().map_err(|e| format!("{e} in '{s}'"))?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(expr),
+            Some((token, column)) => {
+                Err(format!("Unexpected '{token}' at column {column} in '{s}'"))
+            }
+        }
+    }
+}
+
+/// Lexical unit of an expression.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Symbol(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Identifier(name) => write!(f, "{name}"),
+            Self::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+/// Tokens of `s`, with their column (from 1).
+fn tokenize(s: &str) -> Result<Vec<(Token, usize)>, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Exponent, e.g. 1e-3
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let number: String = chars[start..i].iter().collect();
+            let value: f64 = number
+                .parse()
+                .map_err(|_| format!("Invalid number '{number}' at column {column}"))?;
+            if !value.is_finite() {
+                return Err(format!("Number '{number}' at column {column} is too large"));
+            }
+            tokens.push((Token::Number(value), column));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Identifier(chars[start..i].iter().collect()), column));
+        } else if "+-*/^()".contains(c) {
+            tokens.push((Token::Symbol(c), column));
+            i += 1;
+        } else {
+            return Err(format!("Unexpected character '{c}' at column {column}"));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent parser.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize, // number of characters of the expression
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Column of the next token, or just after the end of the expression.
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end + 1, |(_, column)| *column)
+    }
+
+    /// Consume `symbol` if it is the next token.
+    fn eat(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), String> {
+        if self.eat(symbol) {
+            Ok(())
+        } else {
+            Err(format!("Expected '{symbol}' at column {}", self.column()))
+        }
+    }
+
+    /// sum := product (('+' | '-') product)*
+    fn sum(&mut self) -> Result<CostExpression, String> {
+        let mut expr = self.product()?;
+        loop {
+            let op = if self.eat('+') {
+                BinaryOp::Add
+            } else if self.eat('-') {
+                BinaryOp::Sub
+            } else {
+                return Ok(expr);
+            };
+            expr = CostExpression::Binary(op, Box::new(expr), Box::new(self.product()?));
+        }
+    }
+
+    /// product := unary (('*' | '/') unary)*
+    fn product(&mut self) -> Result<CostExpression, String> {
+        let mut expr = self.unary()?;
+        loop {
+            let op = if self.eat('*') {
+                BinaryOp::Mul
+            } else if self.eat('/') {
+                BinaryOp::Div
+            } else {
+                return Ok(expr);
+            };
+            expr = CostExpression::Binary(op, Box::new(expr), Box::new(self.unary()?));
+        }
+    }
+
+    /// unary := '-' unary | power
+    fn unary(&mut self) -> Result<CostExpression, String> {
+        if self.eat('-') {
+            Ok(CostExpression::Neg(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    /// power := primary ('^' unary)?
+    fn power(&mut self) -> Result<CostExpression, String> {
+        let base = self.primary()?;
+        if self.eat('^') {
+            Ok(CostExpression::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// primary := number | function '(' sum ')' | parameter | '(' sum ')'
+    fn primary(&mut self) -> Result<CostExpression, String> {
+        let column = self.column();
+        match self.next().map(|(token, _)| token) {
+            Some(Token::Number(value)) => Ok(CostExpression::Number(value)),
+            Some(Token::Identifier(name)) => {
+                if let Some(function) = Function::ALL.into_iter().find(|f| f.name() == name) {
+                    if self.eat('(') {
+                        let argument = self.sum()?;
+                        self.expect(')')?;
+                        return Ok(CostExpression::Function(function, Box::new(argument)));
+                    }
+                }
+                Ok(CostExpression::Parameter(name))
+            }
+            Some(Token::Symbol('(')) => {
+                let expr = self.sum()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some(token) => Err(format!("Unexpected '{token}' at column {column}")),
+            None => Err(format!("Unexpected end of expression at column {column}")),
+        }
+    }
+}
+
+/// Symbolic logical costs of an algorithm.
+///
+/// The optional counts are 0 when not given.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CostModel {
+    /// Number of logical qubits.
+    pub qubits: CostExpression,
+    /// Number of CX gates.
+    pub cx: CostExpression,
+    /// Number of CCX gates.
+    pub ccx: CostExpression,
+    /// Number of compute-AND.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub and_compute: Option<CostExpression>,
+    /// Number of uncompute-AND.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub and_uncompute: Option<CostExpression>,
+    /// Number of measurements.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measurements: Option<CostExpression>,
+    /// Number of resets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resets: Option<CostExpression>,
+}
+
+impl CostModel {
+    /// Model with the given qubits, CX and CCX counts.
+    #[must_use]
+    pub fn new(qubits: CostExpression, cx: CostExpression, ccx: CostExpression) -> Self {
+        Self {
+            qubits,
+            cx,
+            ccx,
+            and_compute: None,
+            and_uncompute: None,
+            measurements: None,
+            resets: None,
+        }
+    }
+
+    /// Logical counts for the given parameter values (counts are rounded up).
+    pub fn counts(&self, parameters: &HashMap<String, f64>) -> Result<LogicalCounts, String> {
+        let optional = |expr: &Option<CostExpression>| {
+            expr.as_ref()
+                .map_or(Ok(0), |expr| expr.evaluate_count(parameters))
+        };
+
+        Ok(LogicalCounts::new(
+            self.qubits.evaluate_count(parameters)?,
+            self.cx.evaluate_count(parameters)?,
+            self.ccx.evaluate_count(parameters)?,
+        )
+        .with_logical_ands(optional(&self.and_compute)?, optional(&self.and_uncompute)?)
+        .with_measurements(optional(&self.measurements)?, optional(&self.resets)?))
+    }
+
+    /// Names of the parameters used in the model.
+    #[must_use]
+    pub fn parameters(&self) -> Vec<&str> {
+        let mut names: Vec<_> = [&self.qubits, &self.cx, &self.ccx]
+            .into_iter()
+            .chain(&self.and_compute)
+            .chain(&self.and_uncompute)
+            .chain(&self.measurements)
+            .chain(&self.resets)
+            .flat_map(CostExpression::parameters)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
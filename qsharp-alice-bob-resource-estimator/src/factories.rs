@@ -11,8 +11,10 @@
 //! In the article, the performances for some parameter sets have been
 //! precomputed (see Table III, p. 35). The table is hard-coded in the
 //! implementation of [`Default`] for [`ToffoliBuilder`].
-//! Note that 1/κ₂ = 100 ns and κ₁/κ₂ = 1e-5 are hard-coded (values used in the
-//! precomputation).
+//! Note that κ₁/κ₂ = 1e-5 is hard-coded (value used in the precomputation);
+//! the error probabilities can be scaled to model other values or
+//! uncertainties with [`ToffoliBuilder::with_error_scale`]. The durations
+//! follow κ₂ of the [`CatQubit`].
 
 use num_traits::FromPrimitive;
 use resource_estimator::estimates::{self, FactoryBuilder};
@@ -42,7 +44,7 @@ use crate::{code::CodeParameter, CatQubit, RepetitionCode};
 /// [arXiv:2302.06639](https://arxiv.org/abs/2302.06639) (Table III, p. 35) and
 /// are available through [`ToffoliBuilder`]'s [`Default`] trait.
 ///
-/// Value 1/κ₂ = 100 ns and κ₁/κ₂ = 1e-5 are hard-coded.
+/// Value κ₁/κ₂ = 1e-5 is hard-coded.
 #[derive(Clone, PartialEq)]
 pub struct ToffoliFactory {
     code_distance: usize,
@@ -50,6 +52,7 @@ pub struct ToffoliFactory {
     error_probability: f64,
     acceptance_probability: f64,
     steps: usize,
+    time_unit: f64, // 1/κ₂ [nanoseconds]
 }
 
 impl ToffoliFactory {
@@ -59,6 +62,30 @@ impl ToffoliFactory {
         self.error_probability
     }
 
+    /// Distance of the repetition code used in the factory.
+    #[must_use]
+    pub fn code_distance(&self) -> usize {
+        self.code_distance
+    }
+
+    /// Average number of photons |α|² in each cat of the factory.
+    #[must_use]
+    pub fn alpha_sq(&self) -> f64 {
+        self.alpha_sq
+    }
+
+    /// Probability that the magic state preparation is accepted.
+    #[must_use]
+    pub fn acceptance_probability(&self) -> f64 {
+        self.acceptance_probability
+    }
+
+    /// Number of steps of the magic state preparation.
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
     /// Space-time volume of the factory (including retries).
     #[must_use]
     pub fn normalized_volume(&self) -> u64 {
@@ -97,13 +124,12 @@ impl estimates::Factory for ToffoliFactory {
     /// as in [arXiv:2302.06639](https://arxiv.org/abs/2302.06639) the CNOTs are
     /// implemented in an adiabatic way, with a gate time of 89.2/(κ₂|α|²) (see
     /// p. 32).
-    /// 1/κ₂ = 100 ns and κ₁/κ₂ = 1e-5 are hard-coded.
+    /// κ₁/κ₂ = 1e-5 is hard-coded; 1/κ₂ is the one of the qubits.
     ///
     /// The factory is heralded, this duration take into account that retry
     /// might be required.
     fn duration(&self) -> u64 {
-        // If you change it, you also need to recompute the default factories.
-        let t = 100.0; // 1/κ₂ [nanoseconds]
+        let t = self.time_unit;
 
         // The more accurate # of time steps 89.2 was taken from the Github code
         // (vs 89 in arXiv:2302.06639 (p. 32))
@@ -167,6 +193,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 1.05e-3,
                 steps: 23,
                 acceptance_probability: 0.84,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 3,
@@ -174,6 +201,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 1.02e-4,
                 steps: 29,
                 acceptance_probability: 0.745,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 3,
@@ -181,6 +209,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 8.14e-5,
                 steps: 35,
                 acceptance_probability: 0.66,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 5,
@@ -188,6 +217,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 4.62e-6,
                 steps: 46,
                 acceptance_probability: 0.456,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 5,
@@ -195,6 +225,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 7.00e-7,
                 steps: 53,
                 acceptance_probability: 0.362,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 5,
@@ -202,6 +233,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 5.36e-7,
                 steps: 60,
                 acceptance_probability: 0.288,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 7,
@@ -209,6 +241,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 6.14e-8,
                 steps: 73,
                 acceptance_probability: 0.148,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 7,
@@ -216,6 +249,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 8.40e-9,
                 steps: 81,
                 acceptance_probability: 0.105,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 7,
@@ -223,6 +257,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 5.16e-9,
                 steps: 89,
                 acceptance_probability: 0.0727,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 9,
@@ -230,6 +265,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 2.28e-9,
                 steps: 104,
                 acceptance_probability: 0.0262,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 9,
@@ -237,6 +273,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 2.30e-10,
                 steps: 113,
                 acceptance_probability: 0.0154,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 9,
@@ -244,6 +281,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 7.36e-11,
                 steps: 122,
                 acceptance_probability: 0.00975,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 19,
@@ -251,6 +289,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 7.90e-12,
                 steps: 9576,
                 acceptance_probability: 1.0,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 21,
@@ -258,6 +297,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 5.40e-13,
                 steps: 14112,
                 acceptance_probability: 1.0,
+                time_unit: 100.0,
             },
             ToffoliFactory {
                 code_distance: 23,
@@ -265,6 +305,7 @@ impl Default for ToffoliBuilder {
                 error_probability: 3.74e-14,
                 steps: 21344,
                 acceptance_probability: 1.0,
+                time_unit: 100.0,
             },
         ];
 
@@ -281,29 +322,54 @@ impl Default for ToffoliBuilder {
     }
 }
 
+impl ToffoliBuilder {
+    /// Multiply the error probabilities of the factories by `scale`, e.g. to
+    /// model a κ₁/κ₂ different from the one of the precomputation, or the
+    /// uncertainty on the precomputed values.
+    #[must_use]
+    pub fn with_error_scale(self, scale: f64) -> Self {
+        Self {
+            factories: self
+                .factories
+                .into_iter()
+                .map(|factory| ToffoliFactory {
+                    error_probability: factory.error_probability * scale,
+                    ..factory
+                })
+                .collect(),
+            lowest_error_probability: self.lowest_error_probability * scale,
+        }
+    }
+}
+
 impl FactoryBuilder<RepetitionCode> for ToffoliBuilder {
     type Factory = ToffoliFactory;
 
     /// Provide a sorted (by volume) list of factories that reach the target
-    /// logical error rate.
+    /// logical error rate, or `None` if the target is below the error
+    /// probability of all the factories.
     fn find_factories(
         &self,
         _ftp: &RepetitionCode,
-        _qubit: &Rc<CatQubit>,
+        qubit: &Rc<CatQubit>,
         _magic_state_type: usize,
         output_error_rate: f64,
         _max_code_parameter: &CodeParameter,
     ) -> Option<Vec<Cow<Self::Factory>>> {
-        assert!(
-            output_error_rate > self.lowest_error_probability,
-            "Requested error probability is too low"
-        );
+        // Requested error probability is too low
+        if output_error_rate <= self.lowest_error_probability {
+            return None;
+        }
 
         let mut factories: Vec<_> = self
             .factories
             .iter()
-            .filter_map(|factory| {
-                (factory.error_probability <= output_error_rate).then_some(Cow::Borrowed(factory))
+            .filter(|factory| factory.error_probability <= output_error_rate)
+            .map(|factory| {
+                Cow::Owned(ToffoliFactory {
+                    time_unit: qubit.time_unit(),
+                    ..factory.clone()
+                })
             })
             .collect();
         factories.sort_unstable();
@@ -0,0 +1,158 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Chip area of an estimate, and number of chips and modules.
+//!
+//! Every cat qubit is made of a resonator (storing the cat) and of a buffer
+//! (stabilizing it). The ancilla cats of the repetition code, (d - 1) out of
+//! the (2d - 1) cats of a patch of distance d, also need readout circuitry,
+//! and the routing qubits (see [`crate::layout`]) need couplers to the qubits
+//! they connect. The area of the components is increased by a packing factor
+//! for spacing and wiring, and split into chips of limited area, themselves
+//! assembled into modules.
+//!
+//! The default areas of [`FootprintModel`] do not come from a published chip
+//! layout: they are sized from the geometry of the components. The resonator
+//! of a cat is a quarter-wave coplanar resonator at a few GHz, a few
+//! millimetres long on silicon, meandered in about 0.5 mm². The buffer, a
+//! shorter resonator with its Josephson junction and pump line, takes about
+//! 0.3 mm², the readout resonator and filter of an ancilla about 0.2 mm², and
+//! the couplers of a routing cat about 0.1 mm². The packing factor of 1.5
+//! accounts for the ground plane, bond pads and vias between the components;
+//! a chip is at most 20 mm × 20 mm, the size of a common die, and a module
+//! holds 4 chips. These figures should be replaced by the areas of the chip
+//! design being considered (see `--footprint-model` on the command line).
+
+use std::fmt::Display;
+
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::AliceAndBobEstimates;
+
+/// Area of the components, in mm², and chip-size limits.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FootprintModel {
+    /// Area of the resonator of a cat qubit.
+    pub cat_area: f64,
+    /// Area of the buffer of a cat qubit.
+    pub buffer_area: f64,
+    /// Area of the readout circuitry of an ancilla cat.
+    pub ancilla_area: f64,
+    /// Area of the couplers of a routing cat.
+    pub routing_area: f64,
+    /// Factor applied to the area of the components, for spacing and wiring.
+    pub packing_factor: f64,
+    /// Maximum area of a chip.
+    pub max_chip_area: f64,
+    /// Number of chips in a module.
+    pub chips_per_module: u64,
+}
+
+impl Default for FootprintModel {
+    fn default() -> Self {
+        Self {
+            cat_area: 0.5,
+            buffer_area: 0.3,
+            ancilla_area: 0.2,
+            routing_area: 0.1,
+            packing_factor: 1.5,
+            max_chip_area: 400.0,
+            chips_per_module: 4,
+        }
+    }
+}
+
+/// Chip area of an estimate, in mm², by component.
+#[derive(Clone, Debug, Serialize)]
+pub struct Footprint {
+    /// Name of the layout of the qubits.
+    pub layout: String,
+    /// Area of the cat resonators.
+    pub cat_area: f64,
+    /// Area of the buffers.
+    pub buffer_area: f64,
+    /// Area of the readout circuitry of the ancillas.
+    pub ancilla_area: f64,
+    /// Area of the couplers of the routing qubits.
+    pub routing_area: f64,
+    /// Total area, packing included.
+    pub total_area: f64,
+    /// Number of chips.
+    pub chips: u64,
+    /// Number of modules.
+    pub modules: u64,
+}
+
+impl FootprintModel {
+    /// Compute the chip area of `estimates`.
+    #[must_use]
+    pub fn estimate(&self, estimates: &AliceAndBobEstimates) -> Footprint {
+        let to_f64 = |n: u64| n.to_f64().expect("can't convert");
+        // (d - 1) ancillas in a patch of (2d - 1) cats
+        let ancilla_fraction = |distance: u64| {
+            to_f64(distance.saturating_sub(1)) / to_f64((2 * distance).saturating_sub(1).max(1))
+        };
+
+        let report = estimates.report();
+        let cats = to_f64(report.physical_qubits.total);
+        let patches = to_f64(report.counts.logical_qubits * report.code.physical_qubits);
+        let factory_cats = to_f64(report.physical_qubits.factories);
+        let factory_ancillas = report.factory.as_ref().map_or(0.0, |factory| {
+            let distance = factory.distance.try_into().expect("can't convert");
+            factory_cats * ancilla_fraction(distance)
+        });
+        let ancillas = patches * ancilla_fraction(report.code.distance) + factory_ancillas;
+
+        let cat_area = cats * self.cat_area;
+        let buffer_area = cats * self.buffer_area;
+        let ancilla_area = ancillas * self.ancilla_area;
+        let routing_area = to_f64(report.physical_qubits.routing) * self.routing_area;
+        let total_area =
+            (cat_area + buffer_area + ancilla_area + routing_area) * self.packing_factor;
+
+        let chips = (total_area / self.max_chip_area)
+            .ceil()
+            .to_u64()
+            .expect("number of chips is too large")
+            .max(1);
+
+        Footprint {
+            layout: report.layout,
+            cat_area,
+            buffer_area,
+            ancilla_area,
+            routing_area,
+            total_area,
+            chips,
+            modules: chips.div_ceil(self.chips_per_module.max(1)),
+        }
+    }
+}
+
+impl Display for Footprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "─────────────────────────────")?;
+        writeln!(f, "layout:              {}", self.layout)?;
+        writeln!(f, "total area:          {:.2} cm²", self.total_area / 100.0)?;
+        writeln!(f, "  cat resonators:    {:.2} cm²", self.cat_area / 100.0)?;
+        writeln!(
+            f,
+            "  buffers:           {:.2} cm²",
+            self.buffer_area / 100.0
+        )?;
+        writeln!(
+            f,
+            "  ancilla readout:   {:.2} cm²",
+            self.ancilla_area / 100.0
+        )?;
+        writeln!(
+            f,
+            "  routing couplers:  {:.2} cm²",
+            self.routing_area / 100.0
+        )?;
+        writeln!(f, "# chips:             {}", self.chips)?;
+        writeln!(f, "# modules:           {}", self.modules)?;
+        writeln!(f, "─────────────────────────────")
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Layout of the logical qubits on the processor, and its routing overhead.
+//!
+//! The layout sets the number of "horizontal" routing qubits, counted as
+//! logical qubits next to the compute qubits (see
+//! [`LogicalCounts`](crate::LogicalCounts)), and of "vertical" routing qubits,
+//! counted as physical qubits and added to the estimates (see
+//! [`AliceAndBobEstimates`](crate::AliceAndBobEstimates)). The layout of a
+//! count is set with [`LogicalCounts::with_layout`](crate::LogicalCounts::with_layout),
+//! and defaults to [`StandardLayout`].
+//!
+//! The alternative layouts trade routing qubits for routing contention, which
+//! is not modelled as the counter does not consider parallelism. Only
+//! [`StandardLayout`] comes from the literature: the formulas of
+//! [`DenseLayout`], [`SharedBusLayout`] and [`DoubleSidedFactoriesLayout`] are
+//! illustrative variations of it, derived from their geometry as described
+//! below, and have not been validated against a routing schedule.
+//!
+//! The names of the layouts are the values of the `--layout` option of the
+//! command line.
+
+use std::{fmt::Debug, sync::Arc};
+
+/// Number of routing qubits of a layout.
+pub trait RoutingLayout: Debug {
+    /// Name of the layout.
+    fn name(&self) -> &'static str;
+
+    /// Number of "horizontal" routing logical qubits for `compute_qubits`
+    /// logical compute qubits, and whether magic state factories are used.
+    fn horizontal_routing_qubits(&self, compute_qubits: u64, factories: bool) -> u64;
+
+    /// Number of "vertical" routing physical qubits, for `logical_qubits`
+    /// logical qubits (horizontal routing qubits included) and
+    /// `factory_copies` magic state factories.
+    fn vertical_routing_qubits(&self, logical_qubits: u64, factory_copies: u64) -> u64;
+
+    /// Number of compute qubits in a column of the layout, to compute the
+    /// distance between qubits (see [`crate::counter::placement`]).
+    fn qubits_per_column(&self) -> u64;
+}
+
+/// Layout of [arXiv:2302.06639](https://arxiv.org/abs/2302.06639) (p. 27):
+/// two rows of compute qubits on both sides of a row of routing qubits, the
+/// factories on top, and "vertical" routing qubits along the whole layout.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StandardLayout;
+
+impl RoutingLayout for StandardLayout {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+
+    fn horizontal_routing_qubits(&self, compute_qubits: u64, factories: bool) -> u64 {
+        // One routing qubit per column, and one in between the compute and
+        // factory parts
+        compute_qubits.div_ceil(2) + u64::from(factories)
+    }
+
+    fn vertical_routing_qubits(&self, logical_qubits: u64, factory_copies: u64) -> u64 {
+        // Formula from arXiv: 2302.06639, p. 27; each factory spans 5 logical
+        // qubits
+        2 * (3 * (logical_qubits + factory_copies * 5)).saturating_sub(1)
+    }
+
+    fn qubits_per_column(&self) -> u64 {
+        2
+    }
+}
+
+/// Denser packing: each routing qubit serves four compute qubits, and a
+/// single line of "vertical" routing qubits.
+///
+/// Illustrative: half of the routing qubits of [`StandardLayout`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DenseLayout;
+
+impl RoutingLayout for DenseLayout {
+    fn name(&self) -> &'static str {
+        "dense"
+    }
+
+    fn horizontal_routing_qubits(&self, compute_qubits: u64, factories: bool) -> u64 {
+        compute_qubits.div_ceil(4) + u64::from(factories)
+    }
+
+    fn vertical_routing_qubits(&self, logical_qubits: u64, factory_copies: u64) -> u64 {
+        // One line instead of the two of the standard layout
+        (3 * (logical_qubits + factory_copies * 5)).saturating_sub(1)
+    }
+
+    fn qubits_per_column(&self) -> u64 {
+        4
+    }
+}
+
+/// Single row of compute qubits along a routing bus shared by all the qubits
+/// and the factories: one routing qubit per compute qubit, and no "vertical"
+/// routing qubits.
+///
+/// Illustrative: the bus is modelled as a line of logical qubits.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SharedBusLayout;
+
+impl RoutingLayout for SharedBusLayout {
+    fn name(&self) -> &'static str {
+        "shared-bus"
+    }
+
+    fn horizontal_routing_qubits(&self, compute_qubits: u64, factories: bool) -> u64 {
+        compute_qubits + u64::from(factories)
+    }
+
+    fn vertical_routing_qubits(&self, _logical_qubits: u64, _factory_copies: u64) -> u64 {
+        0
+    }
+
+    fn qubits_per_column(&self) -> u64 {
+        1
+    }
+}
+
+/// Standard layout with the factories split on both sides of the compute
+/// qubits: one more routing qubit between the compute and factory parts, but
+/// "vertical" routing qubits along half of the factories only.
+///
+/// Illustrative: [`StandardLayout`] with half of the factories on each side.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DoubleSidedFactoriesLayout;
+
+impl RoutingLayout for DoubleSidedFactoriesLayout {
+    fn name(&self) -> &'static str {
+        "double-sided-factories"
+    }
+
+    fn horizontal_routing_qubits(&self, compute_qubits: u64, factories: bool) -> u64 {
+        compute_qubits.div_ceil(2) + 2 * u64::from(factories)
+    }
+
+    fn vertical_routing_qubits(&self, logical_qubits: u64, factory_copies: u64) -> u64 {
+        StandardLayout.vertical_routing_qubits(logical_qubits, factory_copies.div_ceil(2))
+    }
+
+    fn qubits_per_column(&self) -> u64 {
+        2
+    }
+}
+
+/// Shared layout of a [`LogicalCounts`](crate::LogicalCounts).
+#[derive(Clone, Debug)]
+pub(crate) struct Layout(pub(crate) Arc<dyn RoutingLayout + Send + Sync>);
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self(Arc::new(StandardLayout))
+    }
+}
@@ -18,21 +18,27 @@
 //! ### Assumes:
 //! - architecture as described in
 //!   [arXiv:2302.06639](https://arxiv.org/abs/2302.06639)
-//! - 1/κ₂ = 100 ns
-//! - κ₁/κ₂ = 1e-5
+//! - 1/κ₂ = 100 ns and κ₁/κ₂ = 1e-5 by default (see [`analysis`] for the
+//!   impact of these and other model parameters)
 //! - no saturation of bit-flip
 //! - simplified gate counting, when translating from Q# (no consequences for
 //!   modular arithmetic circuits, approximation in general):
 //!   * 1-qubit Clifford gates are free
 //!   * CX, CY, CZ are count as CX
+//!   * CCX with a target in |0⟩ and its measurement-based uncomputation are
+//!     counted as logical ANDs
 //!   * no parallelism considered
+//!   * all-to-all connectivity by default (see [`counter::placement`])
 //!
 //! ### Takes:
 //! - specification of the algorithmic required resources, either entered
-//!   directly, either deduced from a Q# file (see `example/from_qsharp.rs`).
+//!   directly, either deduced from a Q# file (see `example/from_qsharp.rs`),
+//!   either evaluated from a symbolic cost model (see [`expression`]).
 //!   * number of logical qubits
 //!   * number of logical CX
 //!   * number of logical CCX
+//!   * number of logical compute-AND and uncompute-AND (optional)
+//!   * number of logical measurements and resets (optional)
 //! - error budget:
 //!   * maximum total topological error probability
 //!   * maximum total error probability from magic states preparations
@@ -46,17 +52,32 @@
 //!     * repetition code distance
 //!     * average number of photons |α|² in each cat
 //! - fraction of qubits assigned to the magic state factory
+//! - number of physical qubits used for routing, depending on the layout (see
+//!   [`layout`])
+//! - average number of logical qubits in use (see [`counter::volume`])
+//! - optionally, control lines, refrigerators and energy (see [`cryo`])
+//! - optionally, chip area and number of chips and modules (see [`footprint`])
+//! - sensitivity of the estimates to the model parameters (see
+//!   [`analysis::sensitivity`]), and their spread when the parameters are
+//!   uncertain (see [`analysis::uncertainty`])
 //!
 //! *Author: Mathias Soeken*
 
+pub use analysis::ModelParameters;
 pub use code::RepetitionCode;
-pub use counter::LogicalCounts;
-pub use estimates::AliceAndBobEstimates;
+pub use counter::{Connectivity, CycleCosts, IdleErrorModel, LogicalCounts, Placement};
+pub use estimates::{AliceAndBobEstimates, EstimateReport};
+pub use expression::{CostExpression, CostModel};
 pub use factories::ToffoliBuilder;
 pub use qubit::CatQubit;
 
+pub mod analysis;
 pub mod code;
 pub mod counter;
+pub mod cryo;
 pub mod estimates;
+pub mod expression;
 pub mod factories;
+pub mod footprint;
+pub mod layout;
 pub mod qubit;
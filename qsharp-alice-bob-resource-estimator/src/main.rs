@@ -5,13 +5,30 @@
 //! computer with repetition code. The command-line is self documented, please
 //! use it with subcommand `help` to learn its usage.
 
-use clap::{Args, Parser, Subcommand};
-use std::rc::Rc;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufReader, BufWriter},
+};
 
+use num_traits::FromPrimitive;
+use qsharp_alice_bob_resource_estimator::analysis::{
+    BudgetSplit, BudgetSweep, Constraints, CostModelWorkload, Objective, QubitRequirement,
+    UncertainParameters, Workload,
+};
+use qsharp_alice_bob_resource_estimator::cryo::CryoModel;
+use qsharp_alice_bob_resource_estimator::estimates::CompactEstimates;
+use qsharp_alice_bob_resource_estimator::footprint::FootprintModel;
+use qsharp_alice_bob_resource_estimator::layout::{
+    DenseLayout, DoubleSidedFactoriesLayout, SharedBusLayout, StandardLayout,
+};
 use qsharp_alice_bob_resource_estimator::{
-    AliceAndBobEstimates, CatQubit, LogicalCounts, RepetitionCode, ToffoliBuilder,
+    AliceAndBobEstimates, Connectivity, CostModel, IdleErrorModel, LogicalCounts, ModelParameters,
 };
-use resource_estimator::estimates::{ErrorBudget, PhysicalResourceEstimation};
+use resource_estimator::estimates::ErrorBudget;
+use serde::Serialize;
+use serde_json::json;
 
 /// Resource estimator for Alice & Bob's architecture (cats + repetition code).
 #[derive(Parser)]
@@ -21,13 +38,110 @@ struct Cli {
     #[arg(short, long)]
     frontier: bool,
 
+    /// Format of the results.
+    #[arg(long, value_enum, default_value_t = Output::Text)]
+    output: Output,
+
+    /// Number of decimals of the physical qubits and runtime in the text
+    /// and compact outputs.
+    #[arg(long, default_value_t = 2, value_name = "N")]
+    precision: usize,
+
+    #[command(flatten)]
+    hardware: Hardware,
+
+    #[command(flatten)]
+    counting: Counting,
+
     #[command(flatten)]
     budget: Budget,
 
+    #[command(flatten)]
+    model: Model,
+
+    #[command(flatten)]
+    analysis: AnalysisArgs,
+
+    #[command(flatten)]
+    limits: Limits,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Args)]
+struct Counting {
+    /// Only count logical errors on qubits while they are allocated (and on
+    /// routing qubits), instead of on the peak number of qubits during the
+    /// whole runtime.
+    #[arg(long)]
+    live_qubit_errors: bool,
+
+    /// Layout of the logical qubits, which sets the number of routing qubits.
+    #[arg(long, value_enum, default_value_t = LayoutArg::Standard)]
+    layout: LayoutArg,
+
+    /// Connectivity between the logical qubits (`file`, also with `--samples`
+    /// and `--explore`, and `trace` only, as the other subcommands record no
+    /// interactions between qubits).
+    #[arg(long, value_enum, default_value_t = ConnectivityArg::AllToAll)]
+    connectivity: ConnectivityArg,
+
+    /// Place the qubits counted from Q# to reduce the routing cost, instead of
+    /// in allocation order (`file` and `trace` only, as `--connectivity`).
+    #[arg(long)]
+    optimize_placement: bool,
+}
+
+impl Counting {
+    /// `count` with the layout, connectivity and idle error model of the
+    /// options.
+    ///
+    /// The connectivity and the placement depend on the interactions between
+    /// qubits, which are only recorded when counting a Q# file or a trace:
+    /// they are rejected for counts without interactions, on which they have
+    /// no effect.
+    fn apply(&self, count: LogicalCounts) -> Result<LogicalCounts, String> {
+        if count.interactions().is_empty() {
+            if matches!(self.connectivity, ConnectivityArg::Swaps) {
+                return Err(
+                    "--connectivity swaps requires the interactions between qubits, which are \
+                     only recorded by the `file` and `trace` subcommands"
+                        .to_string(),
+                );
+            }
+            if self.optimize_placement {
+                return Err(
+                    "--optimize-placement requires the interactions between qubits, which are \
+                     only recorded by the `file` and `trace` subcommands"
+                        .to_string(),
+                );
+            }
+        }
+
+        let count = match self.layout {
+            LayoutArg::Standard => count.with_layout(StandardLayout),
+            LayoutArg::Dense => count.with_layout(DenseLayout),
+            LayoutArg::SharedBus => count.with_layout(SharedBusLayout),
+            LayoutArg::DoubleSidedFactories => count.with_layout(DoubleSidedFactoriesLayout),
+        };
+        let count = count.with_connectivity(match self.connectivity {
+            ConnectivityArg::AllToAll => Connectivity::AllToAll,
+            ConnectivityArg::Swaps => Connectivity::Swaps,
+        });
+        let count = if self.optimize_placement {
+            count.with_optimized_placement()
+        } else {
+            count
+        };
+        Ok(if self.live_qubit_errors {
+            count.with_idle_error_model(IdleErrorModel::LiveQubits)
+        } else {
+            count
+        })
+    }
+}
+
 #[derive(Args)]
 #[group(required = false, multiple = false)]
 struct Budget {
@@ -41,12 +155,322 @@ struct Budget {
     error_budget: Option<Vec<f64>>,
 }
 
+#[derive(Args)]
+struct Model {
+    /// Ratio κ₁/κ₂ of the cat qubits [default: 1e-5].
+    #[arg(long, value_name = "K1_K2")]
+    k1_k2: Option<f64>,
+
+    /// Two photons loss rate κ₂ of the cat qubits, in s⁻¹ [default: 1e7].
+    #[arg(long, value_name = "K2")]
+    k2: Option<f64>,
+
+    /// Threshold of the repetition code [default: 0.013].
+    #[arg(long)]
+    threshold: Option<f64>,
+
+    /// Prefactor of the logical phase-flip probability [default: 5.6e-2].
+    #[arg(long)]
+    prefactor: Option<f64>,
+
+    /// Factor applied to the duration of the logical operations [default: 1].
+    #[arg(long, value_name = "FACTOR")]
+    cycle_scale: Option<f64>,
+
+    /// Factor applied to the error probabilities of the magic state factories,
+    /// on top of their scaling with κ₁/κ₂ [default: 1].
+    #[arg(long, value_name = "FACTOR")]
+    factory_error_scale: Option<f64>,
+}
+
+impl From<Model> for ModelParameters {
+    fn from(model: Model) -> Self {
+        let default = Self::default();
+        Self {
+            k1_k2: model.k1_k2.unwrap_or(default.k1_k2),
+            k2: model.k2.unwrap_or(default.k2),
+            threshold: model.threshold.unwrap_or(default.threshold),
+            prefactor: model.prefactor.unwrap_or(default.prefactor),
+            cycle_scale: model.cycle_scale.unwrap_or(default.cycle_scale),
+            factory_error_scale: model
+                .factory_error_scale
+                .unwrap_or(default.factory_error_scale),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Analysis {
+    /// Elasticities of the physical qubits and runtime to the model
+    /// parameters
+    Sensitivity,
+    /// Median and confidence intervals of the results for uncertain model
+    /// parameters (requires `--uncertainty`)
+    MonteCarlo,
+}
+
+#[derive(Args)]
+struct Limits {
+    /// Maximum number of physical qubits, routing qubits included: the
+    /// fastest estimate within the limit is kept.
+    #[arg(long, value_name = "QUBITS", conflicts_with_all = ["analysis", "optimize_budget"])]
+    max_qubits: Option<u64>,
+
+    /// Maximum runtime, e.g. `8h`, `2days` or `30min` (nanoseconds without
+    /// unit): the smallest estimate within the limit is kept, unless
+    /// `--max-qubits` is also given.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, conflicts_with_all = ["analysis", "optimize_budget"])]
+    max_runtime: Option<u64>,
+
+    /// Find the largest value of this parameter of the cost model (`model`
+    /// subcommand) within `--max-qubits` and `--max-runtime`, instead of
+    /// estimating the given value.
+    #[arg(long, value_name = "PARAM", conflicts_with_all = ["analysis", "optimize_budget", "error_frontier"])]
+    largest: Option<String>,
+
+    /// Find the hardware requirement on the cat qubits to meet `--max-qubits`
+    /// and `--max-runtime`, instead of estimating the given qubits.
+    #[arg(long, value_enum, conflicts_with_all = ["analysis", "optimize_budget", "error_frontier", "largest"])]
+    requirement: Option<RequirementArg>,
+
+    /// Smallest and largest values searched by `--largest`.
+    #[arg(long, num_args = 2, value_names = ["MIN", "MAX"], default_values_t = [1, 100_000])]
+    search_range: Vec<u64>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RequirementArg {
+    /// Largest κ₁/κ₂
+    #[value(name = "k1-k2")]
+    K1K2,
+    /// Smallest κ₂
+    K2,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ObjectiveArg {
+    /// Number of physical qubits
+    Qubits,
+    /// Runtime
+    Runtime,
+    /// Product of the number of physical qubits and the runtime
+    Product,
+}
+
+#[derive(Args)]
+struct AnalysisArgs {
+    /// Analyse the estimate instead of printing it.
+    #[arg(long, value_enum)]
+    analysis: Option<Analysis>,
+
+    /// Split the total error budget between logical errors and magic states
+    /// to minimize this objective, and print the chosen split.
+    #[arg(long, value_enum, value_name = "OBJECTIVE", conflicts_with_all = ["analysis", "frontier"])]
+    optimize_budget: Option<ObjectiveArg>,
+
+    /// Number of splits tried by `--optimize-budget`.
+    #[arg(long, default_value_t = 99, value_name = "STEPS")]
+    budget_steps: usize,
+
+    /// Show the frontier over the physical qubits, the runtime and the total
+    /// error, sweeping the total error budget, instead of a single result.
+    #[arg(long, conflicts_with_all = ["analysis", "optimize_budget", "frontier", "max_qubits", "max_runtime"])]
+    error_frontier: bool,
+
+    /// Smallest and largest total error budgets of `--error-frontier` (split
+    /// equally between logical errors and magic states).
+    #[arg(long, num_args = 2, value_names = ["MIN_ERROR", "MAX_ERROR"], default_values_t = [1e-4, 0.5])]
+    error_range: Vec<f64>,
+
+    /// Number of total error budgets of `--error-frontier`.
+    #[arg(long, default_value_t = 20)]
+    error_steps: usize,
+
+    /// Relative perturbation of the parameters for `--analysis sensitivity`.
+    #[arg(long, default_value_t = 0.1, value_name = "STEP")]
+    relative_step: f64,
+
+    /// Distributions of the model parameters for `--analysis monte-carlo`
+    /// (JSON file, see `models/uncertainty.json`).
+    #[arg(long, value_name = "UNCERTAINTY_FILE")]
+    uncertainty: Option<String>,
+
+    /// Number of draws of the model parameters.
+    #[arg(long, default_value_t = 1000)]
+    draws: usize,
+
+    /// Seed of the draws.
+    #[arg(long, default_value_t = 0)]
+    draw_seed: u64,
+
+    /// Probability of the confidence intervals.
+    #[arg(long, default_value_t = 0.95)]
+    confidence: f64,
+}
+
+#[derive(Args)]
+struct Hardware {
+    /// Also estimate the control lines, refrigerators and energy (text and
+    /// JSON outputs).
+    #[arg(long)]
+    cryo: bool,
+
+    /// Assumptions on the control hardware and refrigerators (JSON file)
+    /// [default: built-in assumptions].
+    #[arg(long, value_name = "CRYO_MODEL", requires = "cryo")]
+    cryo_model: Option<String>,
+
+    /// Also estimate the chip area and the number of chips and modules (text
+    /// and JSON outputs).
+    #[arg(long)]
+    footprint: bool,
+
+    /// Areas of the components and chip-size limits (JSON file) [default:
+    /// built-in assumptions].
+    #[arg(long, value_name = "FOOTPRINT_MODEL", requires = "footprint")]
+    footprint_model: Option<String>,
+}
+
+impl Budget {
+    /// Error budget given by the options.
+    fn error_budget(&self) -> ErrorBudget {
+        match (self.error_total, &self.error_budget) {
+            (Some(proba), None) => ErrorBudget::new(proba * 0.5, proba * 0.5, 0.0),
+            (None, Some(vec)) => ErrorBudget::new(vec[0], vec[1], vec[2]),
+            // TODO: give default handling to clap.
+            (None, None) => ErrorBudget::new(0.333 * 0.5, 0.333 * 0.5, 0.0),
+            _ => unreachable!("Clap should have caught that!"),
+        }
+    }
+}
+
+/// Optional models of the hardware-facing costs of the estimates.
+struct HardwareModels {
+    cryo: Option<CryoModel>,
+    footprint: Option<FootprintModel>,
+}
+
+impl HardwareModels {
+    fn new(args: Hardware, output: Output) -> Result<Self, anyhow::Error> {
+        if let Output::Compact = output {
+            if args.cryo {
+                anyhow::bail!("--cryo is not supported with --output compact");
+            }
+            if args.footprint {
+                anyhow::bail!("--footprint is not supported with --output compact");
+            }
+        }
+        Ok(Self {
+            cryo: load_model(args.cryo, args.cryo_model)?,
+            footprint: load_model(args.footprint, args.footprint_model)?,
+        })
+    }
+}
+
+/// Model if `enabled`, read from `filename` or with its default assumptions.
+fn load_model<T: Default + serde::de::DeserializeOwned>(
+    enabled: bool,
+    filename: Option<String>,
+) -> Result<Option<T>, anyhow::Error> {
+    match (enabled, filename) {
+        (false, _) => Ok(None),
+        (true, None) => Ok(Some(T::default())),
+        (true, Some(filename)) => Ok(Some(serde_json::from_reader(BufReader::new(File::open(
+            filename,
+        )?))?)),
+    }
+}
+
+/// Answer of a search or an optimization, printed with its estimates.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Answer {
+    /// Split of the error budget minimizing the objective.
+    BudgetSplit(BudgetSplit),
+    /// Largest value of a parameter of the cost model within the limits.
+    Largest { parameter: String, size: u64 },
+    /// Requirement on the cat qubits to meet the limits.
+    Requirement(QubitRequirement),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BudgetSplit(split) => write!(f, "budget split:        {split}"),
+            Self::Largest { parameter, size } => write!(f, "largest {parameter}: {size}"),
+            Self::Requirement(requirement) => write!(f, "{requirement}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Output {
+    /// Human-readable summary
+    Text,
+    /// Full breakdown in JSON
+    Json,
+    /// One line per result
+    Compact,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LayoutArg {
+    /// Layout of arXiv:2302.06639
+    Standard,
+    /// One routing qubit for four compute qubits
+    Dense,
+    /// A single routing bus shared by all the qubits
+    SharedBus,
+    /// Factories on both sides of the compute qubits
+    DoubleSidedFactories,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ConnectivityArg {
+    /// All-to-all connectivity through the routing qubits
+    AllToAll,
+    /// CX between neighbouring columns only, the other ones are routed with
+    /// SWAP gates
+    Swaps,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Read a Q# file
     File {
         /// Path to the Q# file
         filename: String,
+
+        /// Execute the program several times with random measurement
+        /// outcomes, and estimate for the worst case.
+        #[arg(long, value_name = "SAMPLES", conflicts_with = "explore")]
+        samples: Option<usize>,
+
+        /// Seed of the random measurement outcomes.
+        #[arg(long, default_value_t = 0, requires = "samples")]
+        seed: u64,
+
+        /// Explore all the measurement outcomes (up to `MAX_BRANCHES`
+        /// executions), and estimate for the worst case.
+        #[arg(long, value_name = "MAX_BRANCHES")]
+        explore: Option<usize>,
+
+        /// Write the trace of the counted operations into a file (JSON Lines).
+        #[arg(long, value_name = "TRACE_FILE", conflicts_with_all = ["samples", "explore"])]
+        trace: Option<String>,
+    },
+    /// Read a trace written with `file --trace`
+    Trace {
+        /// Path to the trace file
+        filename: String,
+    },
+    /// Evaluate a symbolic cost model (JSON file)
+    Model {
+        /// Path to the cost model
+        filename: String,
+        /// Value of a parameter of the model
+        #[arg(short, long = "param", value_name = "NAME=VALUE", value_parser = parse_parameter)]
+        params: Vec<(String, f64)>,
     },
     /// Compute from listed resources
     Resources {
@@ -56,40 +480,409 @@ enum Commands {
         cx: u64,
         /// Number of Toffoli gates
         ccx: u64,
+        /// Number of logical ANDs computed into a fresh qubit
+        #[arg(long, default_value_t = 0)]
+        and_computes: u64,
+        /// Number of logical ANDs uncomputed by measurement
+        #[arg(long, default_value_t = 0)]
+        and_uncomputes: u64,
+        /// Number of single-qubit measurements
+        #[arg(long, default_value_t = 0)]
+        measurements: u64,
+        /// Number of single-qubit resets
+        #[arg(long, default_value_t = 0)]
+        resets: u64,
     },
 }
 
+/// Parse a `NAME=VALUE` model parameter.
+fn parse_parameter(s: &str) -> Result<(String, f64), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid parameter '{s}', expected NAME=VALUE"))?;
+    let value = value
+        .parse()
+        .map_err(|_| format!("invalid value for parameter '{name}'"))?;
+    Ok((name.trim().to_string(), value))
+}
+
+/// Parse a duration with an optional unit (`ns`, `us`, `ms`, `s`, `min`, `h`
+/// or `days`) into nanoseconds.
+fn parse_duration(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration '{s}'"))?;
+    let scale = match unit.trim() {
+        "" | "ns" => 1.0,
+        "us" | "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        "min" => 60e9,
+        "h" | "hrs" => 3600e9,
+        "d" | "days" => 86400e9,
+        unit => return Err(format!("unknown time unit '{unit}'")),
+    };
+    u64::from_f64((value * scale).round()).ok_or_else(|| format!("invalid duration '{s}'"))
+}
+
+/// Count the logical resources as given by the subcommand.
+fn logical_counts(command: Commands, output: Output) -> Result<LogicalCounts, anyhow::Error> {
+    let count = match command {
+        Commands::File {
+            filename,
+            samples,
+            seed,
+            explore,
+            trace,
+        } => {
+            let sampled = match (samples, explore) {
+                (None, None) => None,
+                (Some(samples), None) => {
+                    Some(LogicalCounts::sample_qsharp(&filename, samples, seed))
+                }
+                (None, Some(max_branches)) => {
+                    Some(LogicalCounts::explore_qsharp(&filename, max_branches))
+                }
+                _ => unreachable!("Clap should have caught that!"),
+            };
+            if let Some(sampled) = sampled {
+                let sampled = sampled.map_err(anyhow::Error::msg)?;
+                // the JSON and compact outputs only hold the estimates
+                if let Output::Text = output {
+                    println!("{sampled}");
+                } else {
+                    eprintln!("{sampled}");
+                }
+                sampled.worst_case()
+            } else if let Some(trace) = trace {
+                let writer = BufWriter::new(File::create(trace)?);
+                LogicalCounts::from_qsharp_with_trace(filename, writer)
+                    .map_err(anyhow::Error::msg)?
+            } else {
+                LogicalCounts::from_qsharp(filename).map_err(anyhow::Error::msg)?
+            }
+        }
+        Commands::Trace { filename } => {
+            LogicalCounts::from_trace(BufReader::new(File::open(filename)?))
+                .map_err(anyhow::Error::msg)?
+        }
+        Commands::Model { filename, params } => {
+            let model: CostModel = serde_json::from_reader(BufReader::new(File::open(filename)?))?;
+            model
+                .counts(&params.into_iter().collect())
+                .map_err(anyhow::Error::msg)?
+        }
+        Commands::Resources {
+            qubits,
+            cx,
+            ccx,
+            and_computes,
+            and_uncomputes,
+            measurements,
+            resets,
+        } => LogicalCounts::new(qubits, cx, ccx)
+            .with_logical_ands(and_computes, and_uncomputes)
+            .with_measurements(measurements, resets),
+    };
+
+    Ok(count)
+}
+
 fn main() -> Result<(), anyhow::Error> {
     let args = Cli::parse();
 
-    let qubit = CatQubit::new();
-    let qec = RepetitionCode::new();
-    let builder = ToffoliBuilder::default();
-    let budget = match (args.budget.error_total, args.budget.error_budget) {
-        (Some(proba), None) => ErrorBudget::new(proba * 0.5, proba * 0.5, 0.0),
-        (None, Some(vec)) => ErrorBudget::new(vec[0], vec[1], vec[2]),
-        // TODO: give default handling to clap.
-        (None, None) => ErrorBudget::new(0.333 * 0.5, 0.333 * 0.5, 0.0),
-        _ => unreachable!("Clap should have caught that!"),
+    let parameters = ModelParameters::from(args.model);
+    let budget = args.budget.error_budget();
+    let total_error = budget.logical() + budget.magic_states() + budget.rotations();
+
+    if let Some(parameter) = &args.limits.largest {
+        let (size, results) = largest_instance(
+            parameter,
+            &args.limits,
+            args.command,
+            &args.counting,
+            &parameters,
+            &budget,
+            args.frontier,
+        )?;
+        let answer = Answer::Largest {
+            parameter: parameter.clone(),
+            size,
+        };
+        let models = HardwareModels::new(args.hardware, args.output)?;
+        return print_results(
+            &results,
+            Some(&answer),
+            args.output,
+            args.precision,
+            &models,
+        );
+    }
+
+    let count = args
+        .counting
+        .apply(logical_counts(args.command, args.output)?)
+        .map_err(anyhow::Error::msg)?;
+    if let Some(analysis) = args.analysis.analysis {
+        return print_analysis(
+            analysis,
+            &args.analysis,
+            args.output,
+            &parameters,
+            &count,
+            &budget,
+        );
+    }
+
+    let models = HardwareModels::new(args.hardware, args.output)?;
+    let constraints = Constraints {
+        max_physical_qubits: args.limits.max_qubits,
+        max_runtime: args.limits.max_runtime,
     };
 
-    let count = match args.command {
-        Commands::File { filename } => {
-            LogicalCounts::from_qsharp(filename).map_err(anyhow::Error::msg)?
+    let mut answer = None;
+    let results: Vec<AliceAndBobEstimates> = if args.analysis.error_frontier {
+        let sweep = BudgetSweep {
+            min_total: args.analysis.error_range[0],
+            max_total: args.analysis.error_range[1],
+            steps: args.analysis.error_steps,
+            ..BudgetSweep::default()
+        };
+        parameters
+            .error_frontier(&count, &sweep)
+            .map_err(anyhow::Error::msg)?
+    } else if let Some(objective) = args.analysis.optimize_budget {
+        let objective = match objective {
+            ObjectiveArg::Qubits => Objective::PhysicalQubits,
+            ObjectiveArg::Runtime => Objective::Runtime,
+            ObjectiveArg::Product => Objective::Product,
+        };
+        let (split, estimates) = parameters
+            .optimize_budget(&count, total_error, objective, args.analysis.budget_steps)
+            .map_err(anyhow::Error::msg)?;
+        answer = Some(Answer::BudgetSplit(split));
+        vec![estimates]
+    } else if let Some(requirement) = args.limits.requirement {
+        let mut requirement = match requirement {
+            RequirementArg::K1K2 => parameters.max_k1_k2(&count, &budget, &constraints),
+            RequirementArg::K2 => parameters.min_k2(&count, &budget, &constraints),
         }
-        Commands::Resources { qubits, cx, ccx } => LogicalCounts::new(qubits, cx, ccx),
+        .map_err(anyhow::Error::msg)?;
+        let estimates = std::mem::take(&mut requirement.estimates);
+        answer = Some(Answer::Requirement(requirement));
+        select(estimates, &constraints, args.frontier)
+    } else if constraints != Constraints::default() {
+        let frontier = parameters
+            .frontier(&count, &budget)
+            .map_err(anyhow::Error::msg)?;
+        let frontier = constraints.filter(frontier).map_err(anyhow::Error::msg)?;
+        select(frontier, &constraints, args.frontier)
+    } else if args.frontier {
+        parameters
+            .frontier(&count, &budget)
+            .map_err(anyhow::Error::msg)?
+    } else {
+        vec![parameters
+            .estimate(&count, &budget)
+            .map_err(anyhow::Error::msg)?]
     };
-    let estimation =
-        PhysicalResourceEstimation::new(qec, Rc::new(qubit), builder, Rc::new(count), budget);
+    print_results(
+        &results,
+        answer.as_ref(),
+        args.output,
+        args.precision,
+        &models,
+    )
+}
 
-    if args.frontier {
-        let results = estimation.build_frontier()?;
-        for r in results {
-            println!("{}", AliceAndBobEstimates::from(r));
-        }
+/// Objective within `constraints`: the fastest estimate within a qubit limit,
+/// the smallest one within a runtime limit.
+fn objective(constraints: &Constraints) -> Objective {
+    if constraints.max_physical_qubits.is_some() {
+        Objective::Runtime
     } else {
-        let result: AliceAndBobEstimates = estimation.estimate()?.into();
-        println!("{result}");
+        Objective::PhysicalQubits
+    }
+}
+
+/// Largest value of `parameter` of the cost model of the `model` subcommand
+/// within the limits, with its estimates (all the estimates within the limits
+/// if `frontier`).
+fn largest_instance(
+    parameter: &str,
+    limits: &Limits,
+    command: Commands,
+    counting: &Counting,
+    parameters: &ModelParameters,
+    budget: &ErrorBudget,
+    frontier: bool,
+) -> Result<(u64, Vec<AliceAndBobEstimates>), anyhow::Error> {
+    let Commands::Model { filename, params } = command else {
+        anyhow::bail!("--largest requires the `model` subcommand");
+    };
+    let model: CostModel = serde_json::from_reader(BufReader::new(File::open(filename)?))?;
+    let workload = CostModelWorkload::new(&model, parameter, params.into_iter().collect())
+        .map_err(anyhow::Error::msg)?;
+    let constraints = Constraints {
+        max_physical_qubits: limits.max_qubits,
+        max_runtime: limits.max_runtime,
+    };
+    let split = BudgetSplit {
+        logical: budget.logical(),
+        magic_states: budget.magic_states(),
+        rotations: budget.rotations(),
+    };
+
+    let largest = parameters
+        .largest_instance(
+            &|size| {
+                workload
+                    .counts(size)
+                    .and_then(|count| counting.apply(count))
+            },
+            limits.search_range[0]..=limits.search_range[1],
+            &split,
+            &constraints,
+        )
+        .map_err(anyhow::Error::msg)?;
+    Ok((
+        largest.size,
+        select(largest.estimates, &constraints, frontier),
+    ))
+}
+
+/// All the `estimates` within `constraints` if `frontier`, or the one
+/// minimizing the objective of the constraints.
+fn select(
+    estimates: Vec<AliceAndBobEstimates>,
+    constraints: &Constraints,
+    frontier: bool,
+) -> Vec<AliceAndBobEstimates> {
+    if frontier {
+        estimates
+    } else {
+        let objective = objective(constraints);
+        estimates
+            .into_iter()
+            .min_by(|a, b| objective.value(a).total_cmp(&objective.value(b)))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Print the result of an analysis of the estimate.
+fn print_analysis(
+    analysis: Analysis,
+    args: &AnalysisArgs,
+    output: Output,
+    parameters: &ModelParameters,
+    count: &LogicalCounts,
+    budget: &ErrorBudget,
+) -> Result<(), anyhow::Error> {
+    match analysis {
+        Analysis::Sensitivity => {
+            let report = parameters
+                .sensitivity(count, budget, args.relative_step)
+                .map_err(anyhow::Error::msg)?;
+            print_report(&report, output)
+        }
+        Analysis::MonteCarlo => {
+            let filename = args
+                .uncertainty
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("--analysis monte-carlo requires --uncertainty"))?;
+            let uncertain: UncertainParameters =
+                serde_json::from_reader(BufReader::new(File::open(filename)?))?;
+            let report = uncertain
+                .propagate(
+                    parameters,
+                    count,
+                    budget,
+                    args.draws,
+                    args.draw_seed,
+                    args.confidence,
+                )
+                .map_err(anyhow::Error::msg)?;
+            print_report(&report, output)
+        }
+    }
+}
+
+/// Print the report of an analysis as text or JSON.
+fn print_report(
+    report: &(impl std::fmt::Display + serde::Serialize),
+    output: Output,
+) -> Result<(), anyhow::Error> {
+    match output {
+        Output::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        Output::Text | Output::Compact => println!("{report}"),
+    }
+    Ok(())
+}
+
+/// Print the `answer`, if any, and the estimates, with `precision` decimals
+/// and their hardware-facing costs for the given `models`.
+///
+/// The JSON output is always an object whose `results` are the estimates,
+/// each with its `cryo` and `footprint` estimates if requested, and whose
+/// `answer` is the answer, if any. The compact output prints the answer on
+/// the standard error, to keep one line per result.
+fn print_results(
+    results: &[AliceAndBobEstimates],
+    answer: Option<&Answer>,
+    output: Output,
+    precision: usize,
+    models: &HardwareModels,
+) -> Result<(), anyhow::Error> {
+    match output {
+        Output::Text => {
+            if let Some(answer) = answer {
+                println!("{answer}");
+            }
+            for r in results {
+                println!("{r:.precision$}");
+                if let Some(cryo) = &models.cryo {
+                    println!("{}", cryo.estimate(r));
+                }
+                if let Some(footprint) = &models.footprint {
+                    println!("{}", footprint.estimate(r));
+                }
+            }
+        }
+        Output::Json => {
+            let values = results
+                .iter()
+                .map(|r| {
+                    let mut value = json!({ "estimates": r });
+                    if let Some(cryo) = &models.cryo {
+                        value["cryo"] = serde_json::to_value(cryo.estimate(r))?;
+                    }
+                    if let Some(footprint) = &models.footprint {
+                        value["footprint"] = serde_json::to_value(footprint.estimate(r))?;
+                    }
+                    Ok(value)
+                })
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            let mut value = json!({ "results": values });
+            if let Some(answer) = answer {
+                value["answer"] = serde_json::to_value(answer)?;
+            }
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
+        Output::Compact => {
+            if let Some(answer) = answer {
+                eprintln!("{answer}");
+            }
+            println!("{}", CompactEstimates::HEADER);
+            for r in results {
+                println!("{:.precision$}", r.compact());
+            }
+        }
     }
 
     Ok(())
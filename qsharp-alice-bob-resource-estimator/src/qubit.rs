@@ -6,25 +6,32 @@
 //!
 //! Cat qubits are characterized by:
 //! - the physical error rate κ₁/κ₂
+//! - the two photons loss rate κ₂, which sets the gates speed
 //! - their average number of photons |α|²
 //!
-//! Default value (and only one compatible with the magic state factories
-//! precomputations) is κ₁/κ₂=1e-5, while |α|² is considered as an error
-//! correction code parameter and not handled in this module (resource estimator
-//! will optimized on it).
+//! Default values (and only ones compatible with the magic state factories
+//! precomputations) are κ₁/κ₂=1e-5 and 1/κ₂ = 100 ns, while |α|² is considered
+//! as an error correction code parameter and not handled in this module
+//! (resource estimator will optimized on it).
 
 /// Struct for cat qubits, stores κ₁/κ₂, the ratio between the one and two
-/// photon loss rates, as it defines the intrinsic physical error rate.
+/// photon loss rates, as it defines the intrinsic physical error rate, and κ₂,
+/// as it defines the gates speed.
+#[derive(Clone, Copy, Debug)]
 #[must_use]
 pub struct CatQubit {
     pub(crate) k1_k2: f64,
+    pub(crate) k2: f64,
 }
 
 impl Default for CatQubit {
-    /// Set κ₁/κ₂ to a default value of 1e-5, as in
+    /// Set κ₁/κ₂ to a default value of 1e-5 and 1/κ₂ to 100 ns, as in
     /// [arXiv:2302.06639](https://arxiv.org/abs/2302.06639) (p. 2).
     fn default() -> Self {
-        Self { k1_k2: 1e-5 }
+        Self {
+            k1_k2: 1e-5,
+            k2: 1e7,
+        }
     }
 }
 
@@ -35,4 +42,38 @@ impl CatQubit {
         // changing this.
         Self::default()
     }
+
+    /// Set κ₁/κ₂.
+    ///
+    /// Note that the precomputed magic state factories (see
+    /// [`crate::ToffoliBuilder`]) assume κ₁/κ₂ = 1e-5; their error
+    /// probabilities can be scaled with
+    /// [`ToffoliBuilder::with_error_scale`](crate::ToffoliBuilder::with_error_scale),
+    /// as done by [`ModelParameters::factories`](crate::ModelParameters::factories).
+    pub fn with_k1_k2(self, k1_k2: f64) -> Self {
+        Self { k1_k2, ..self }
+    }
+
+    /// Set κ₂, in s⁻¹.
+    pub fn with_k2(self, k2: f64) -> Self {
+        Self { k2, ..self }
+    }
+
+    /// Ratio κ₁/κ₂ between the one and two photons loss rates.
+    #[must_use]
+    pub fn k1_k2(&self) -> f64 {
+        self.k1_k2
+    }
+
+    /// Two photons loss rate κ₂, in s⁻¹.
+    #[must_use]
+    pub fn k2(&self) -> f64 {
+        self.k2
+    }
+
+    /// 1/κ₂ in nanoseconds, the time unit of the gates.
+    #[must_use]
+    pub fn time_unit(&self) -> f64 {
+        1e9 / self.k2
+    }
 }
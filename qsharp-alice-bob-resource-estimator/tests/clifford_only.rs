@@ -0,0 +1,67 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Reference case of a workload without magic states (no CCX nor logical
+//! AND), hence without factories.
+
+use std::rc::Rc;
+
+use qsharp_alice_bob_resource_estimator::{
+    AliceAndBobEstimates, CatQubit, LogicalCounts, RepetitionCode, ToffoliBuilder,
+};
+use resource_estimator::estimates::{ErrorBudget, PhysicalResourceEstimation};
+
+fn clifford_only_estimates() -> AliceAndBobEstimates {
+    let estimation = PhysicalResourceEstimation::new(
+        RepetitionCode::new(),
+        Rc::new(CatQubit::new()),
+        ToffoliBuilder::default(),
+        Rc::new(LogicalCounts::new(10, 100, 0)),
+        ErrorBudget::new(0.333 * 0.5, 0.333 * 0.5, 0.0),
+    );
+
+    estimation
+        .estimate()
+        .expect("estimation does not fail")
+        .into()
+}
+
+#[test]
+fn no_factories() {
+    let estimates = clifford_only_estimates();
+
+    assert_eq!(estimates.num_magic_states(0), 0);
+    assert_eq!(estimates.physical_qubits_for_factories(), 0);
+    assert!(estimates.factory_fraction().abs() < f64::EPSILON);
+    assert!(estimates.magic_state_error().abs() < f64::EPSILON);
+    assert!(!estimates.error_breakdown().exceeds_budget);
+
+    // compute and routing qubits only, the routing qubit between the compute
+    // qubits and the factories is not needed
+    assert_eq!(
+        estimates.physical_qubits(),
+        estimates.compute_qubits() + estimates.routing_qubits()
+    );
+    assert_eq!(
+        estimates.routing_qubits(),
+        5 * estimates.logical_patch().physical_qubits() + 2 * (3 * 15 - 1)
+    );
+}
+
+#[test]
+fn display() {
+    let output = clifford_only_estimates().to_string();
+
+    assert!(output.contains("#factories:          0"));
+    assert!(output.contains("factories distance:  - (no magic state)"));
+    assert!(output.contains("factory fraction:    0.00%"));
+}
+
+#[test]
+fn json() {
+    let report = serde_json::to_value(clifford_only_estimates()).expect("serialization");
+
+    assert!(report["factory"].is_null());
+    assert_eq!(report["physical_qubits"]["factories"], 0);
+    assert_eq!(report["counts"]["magic_states"], 0);
+}
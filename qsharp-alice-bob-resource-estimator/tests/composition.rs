@@ -0,0 +1,42 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Composition of logical counts.
+
+use qsharp_alice_bob_resource_estimator::{layout::DenseLayout, LogicalCounts};
+
+#[test]
+fn sum_keeps_first_settings() {
+    let first = LogicalCounts::new(10, 100, 10);
+    let first = first
+        .clone()
+        .with_cycle_costs(first.cycle_costs().scaled(2.0))
+        .with_layout(DenseLayout);
+    let costs = first.cycle_costs();
+
+    let total: LogicalCounts = [first, LogicalCounts::new(20, 50, 5)].into_iter().sum();
+    assert_eq!(total.cycle_costs(), costs);
+    assert_eq!(total.layout().name(), "dense");
+
+    let empty: LogicalCounts = std::iter::empty().sum();
+    assert_eq!(empty.layout().name(), "standard");
+}
+
+#[test]
+fn overflow() {
+    let counts = LogicalCounts::new(10, u64::MAX / 2 + 1, 0);
+
+    assert!(counts.then(&counts).is_err());
+    assert!(counts.parallel(&counts).is_err());
+    assert!(counts.repeat(2).is_err());
+    assert!(counts.repeat(1).is_ok());
+    assert!(LogicalCounts::new(u64::MAX, 0, 0)
+        .parallel(&LogicalCounts::new(1, 0, 0))
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "overflow")]
+fn overflow_operator() {
+    let _ = LogicalCounts::new(10, u64::MAX, 0) * 2;
+}
@@ -0,0 +1,176 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Parsing, printing and evaluation of symbolic cost expressions.
+
+use std::collections::HashMap;
+
+use qsharp_alice_bob_resource_estimator::expression::{BinaryOp, CostExpression};
+
+fn parse(s: &str) -> CostExpression {
+    s.parse()
+        .unwrap_or_else(|e| panic!("'{s}' does not parse: {e}"))
+}
+
+fn evaluate(s: &str, parameters: &[(&str, f64)]) -> f64 {
+    let parameters: HashMap<String, f64> = parameters
+        .iter()
+        .map(|(name, value)| ((*name).to_string(), *value))
+        .collect();
+    parse(s)
+        .evaluate(&parameters)
+        .unwrap_or_else(|e| panic!("'{s}' does not evaluate: {e}"))
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= 1e-12 * expected.abs().max(1.0),
+        "{actual} != {expected}"
+    );
+}
+
+#[test]
+fn precedence() {
+    assert_close(evaluate("1 + 2 * 3", &[]), 7.0);
+    assert_close(evaluate("(1 + 2) * 3", &[]), 9.0);
+    assert_close(evaluate("2 * 3^2", &[]), 18.0);
+    assert_close(evaluate("2^3^2", &[]), 512.0);
+    assert_close(evaluate("8 / 4 / 2", &[]), 1.0);
+    assert_close(evaluate("8 - 4 - 2", &[]), 2.0);
+    assert_close(
+        evaluate("448 * n^3 / w", &[("n", 256.0), ("w", 16.0)]),
+        469_762_048.0,
+    );
+}
+
+#[test]
+fn unary_minus() {
+    assert_close(evaluate("-x^2", &[("x", 3.0)]), -9.0);
+    assert_close(evaluate("(-x)^2", &[("x", 3.0)]), 9.0);
+    assert_close(evaluate("2^-1", &[]), 0.5);
+    assert_close(evaluate("--2", &[]), 2.0);
+    assert_close(evaluate("3 * -2", &[]), -6.0);
+    assert_close(evaluate("1e-3 * n", &[("n", 2000.0)]), 2.0);
+}
+
+#[test]
+fn functions() {
+    assert_close(evaluate("log2(n)", &[("n", 1024.0)]), 10.0);
+    assert_close(evaluate("ln(1)", &[]), 0.0);
+    assert_close(evaluate("sqrt(n) + 1", &[("n", 16.0)]), 5.0);
+    assert_close(evaluate("ceil(7 / 2)", &[]), 4.0);
+    assert_close(evaluate("floor(7 / 2)", &[]), 3.0);
+    assert_close(evaluate("2 * ceil(log2(n))", &[("n", 1000.0)]), 20.0);
+    // a function name without argument is a parameter
+    assert_close(evaluate("ceil + 1", &[("ceil", 2.0)]), 3.0);
+}
+
+#[test]
+fn parameters() {
+    assert_eq!(parse("9 * n + w + 4 - n").parameters(), ["n", "w"]);
+    assert!(parse("1 + 2").evaluate(&HashMap::new()).is_ok());
+    let missing = parse("n + 1")
+        .evaluate(&HashMap::new())
+        .expect_err("n has no value");
+    assert!(missing.contains("'n'"), "{missing}");
+}
+
+#[test]
+fn error_positions() {
+    let error = |s: &str| {
+        s.parse::<CostExpression>()
+            .expect_err("the expression is invalid")
+    };
+
+    assert_eq!(
+        error("1 + $"),
+        "Unexpected character '$' at column 5 in '1 + $'"
+    );
+    assert_eq!(error("1 + * 2"), "Unexpected '*' at column 5 in '1 + * 2'");
+    assert_eq!(error("(1 + 2"), "Expected ')' at column 7 in '(1 + 2'");
+    assert_eq!(error("1 2"), "Unexpected '2' at column 3 in '1 2'");
+    assert_eq!(
+        error("2 *"),
+        "Unexpected end of expression at column 4 in '2 *'"
+    );
+    assert_eq!(
+        error("1.2.3"),
+        "Invalid number '1.2.3' at column 1 in '1.2.3'"
+    );
+    assert_eq!(
+        error("n + 1e999"),
+        "Number '1e999' at column 5 is too large in 'n + 1e999'"
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    // expressions and their printed form
+    for (s, expected) in [
+        ("-x^2", "-x^2"),
+        ("(-x)^2", "(-x)^2"),
+        ("2^-1", "2^(-1)"),
+        ("2^3^2", "2^3^2"),
+        ("(2^3)^2", "(2^3)^2"),
+        ("a-(b-c)", "a - (b - c)"),
+        ("(a - b) - c", "a - b - c"),
+        ("a/(b*c)", "a / (b * c)"),
+        ("(a+b)*c", "(a + b) * c"),
+        ("-(a+b)", "-(a + b)"),
+        ("1e-3*n", "0.001 * n"),
+        ("log2(n)*ln(x)", "log2(n) * ln(x)"),
+        ("ceil(448 * n^3 / w)", "ceil(448 * n^3 / w)"),
+    ] {
+        let expr = parse(s);
+        let printed = expr.to_string();
+        assert_eq!(printed, expected);
+        assert_eq!(parse(&printed), expr, "'{s}' printed as '{printed}'");
+    }
+}
+
+#[test]
+fn negative_constants() {
+    let number = |value: f64| Box::new(CostExpression::try_from(value).expect("finite"));
+    let n = || Box::new(CostExpression::Parameter("n".into()));
+    // expressions built with negative constants, and their printed form
+    for (expr, expected) in [
+        (
+            CostExpression::Binary(BinaryOp::Pow, number(-2.0), number(2.0)),
+            "(-2)^2",
+        ),
+        (
+            CostExpression::Binary(BinaryOp::Pow, number(2.0), number(-1.0)),
+            "2^(-1)",
+        ),
+        (
+            CostExpression::Binary(BinaryOp::Mul, number(-3.0), n()),
+            "-3 * n",
+        ),
+        (
+            CostExpression::Binary(BinaryOp::Sub, n(), number(-1.0)),
+            "n - -1",
+        ),
+        (CostExpression::Neg(number(-2.0)), "--2"),
+    ] {
+        let printed = expr.to_string();
+        assert_eq!(printed, expected);
+        let parameters = HashMap::from([("n".to_string(), 5.0)]);
+        assert_close(
+            parse(&printed)
+                .evaluate(&parameters)
+                .expect("n has a value"),
+            expr.evaluate(&parameters).expect("n has a value"),
+        );
+        assert_eq!(parse(&printed).to_string(), printed);
+    }
+}
+
+#[test]
+fn constants() {
+    assert_eq!(
+        CostExpression::try_from(2.5).map(|expr| expr.to_string()),
+        Ok("2.5".to_string())
+    );
+    assert!(CostExpression::try_from(f64::INFINITY).is_err());
+    assert!(CostExpression::try_from(f64::NAN).is_err());
+}
@@ -0,0 +1,46 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! Routing cost of the interactions recorded by the counter.
+
+use qsc::{interpret::Value, Backend};
+use qsharp_alice_bob_resource_estimator::{Connectivity, LogicalCounts};
+
+/// Call the custom intrinsic `name` of `counter`.
+fn intrinsic(counter: &mut LogicalCounts, name: &str, arg: Value) {
+    counter
+        .custom_intrinsic(name, arg)
+        .expect("the intrinsic is known")
+        .expect("the intrinsic does not fail");
+}
+
+/// Counts of a CX between distant qubits, followed by a `RepeatEstimates`
+/// block of `count` repetitions if `repeated`.
+fn counts(repeated: Option<i64>) -> LogicalCounts {
+    let mut counter = LogicalCounts::default();
+    let qubits: Vec<_> = (0..8).map(|_| counter.qubit_allocate()).collect();
+    counter.cx(qubits[0], qubits[4]);
+    if let Some(count) = repeated {
+        intrinsic(
+            &mut counter,
+            "BeginRepeatEstimatesInternal",
+            Value::Int(count),
+        );
+        counter.cx(qubits[0], qubits[7]);
+        intrinsic(&mut counter, "EndRepeatEstimatesInternal", Value::unit());
+    }
+    counter.with_connectivity(Connectivity::Swaps)
+}
+
+#[test]
+fn zero_repeat_has_no_routing() {
+    let reference = counts(None);
+    let repeated = counts(Some(0));
+
+    assert!(reference.routing_cycles() > 0.0);
+    assert_eq!(repeated.interactions(), reference.interactions());
+    assert!((repeated.routing_cycles() - reference.routing_cycles()).abs() < f64::EPSILON);
+
+    // a repeated body is routed
+    assert!(counts(Some(2)).routing_cycles() > reference.routing_cycles());
+}
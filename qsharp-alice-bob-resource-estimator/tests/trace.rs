@@ -0,0 +1,50 @@
+// Copyright (c) Alice & Bob.
+// Licensed under the MIT License.
+
+//! A trace counts as the program it was recorded from.
+
+use qsc::{interpret::Value, Backend};
+use qsharp_alice_bob_resource_estimator::{counter::TraceRecorder, LogicalCounts};
+use resource_estimator::estimates::{ErrorBudget, Overhead};
+
+/// Small program with a custom intrinsic without cost.
+fn program(backend: &mut impl Backend) {
+    let qubits: Vec<_> = (0..3).map(|_| backend.qubit_allocate()).collect();
+    backend.cx(qubits[0], qubits[1]);
+    backend
+        .custom_intrinsic("GlobalPhase", Value::Double(0.5))
+        .expect("GlobalPhase is known")
+        .expect("GlobalPhase does not fail");
+    backend.ccx(qubits[0], qubits[1], qubits[2]);
+    backend.swap(qubits[0], qubits[2]);
+    for q in qubits {
+        backend.qubit_release(q);
+    }
+}
+
+/// Quantities of `counts` given to the estimation.
+fn summary(counts: &LogicalCounts) -> (u64, u64, u64, f64) {
+    let budget = ErrorBudget::new(0.1, 0.1, 0.0);
+    (
+        counts.logical_qubits(),
+        counts.logical_depth(&budget),
+        counts.num_magic_states(&budget, 0),
+        counts.live_qubit_cycles(),
+    )
+}
+
+#[test]
+fn global_phase_round_trip() {
+    let mut counter = LogicalCounts::default();
+    program(&mut counter);
+
+    let mut trace = vec![];
+    let mut recorder = TraceRecorder::new(&mut trace);
+    program(&mut recorder);
+    let written = recorder.finish().expect("the trace is written");
+
+    let recounted = LogicalCounts::from_trace(trace.as_slice()).expect("the trace is valid");
+    assert_eq!(summary(&written), summary(&counter));
+    assert_eq!(summary(&recounted), summary(&counter));
+    assert_eq!(recounted.interactions(), counter.interactions());
+}